@@ -0,0 +1,165 @@
+// What `Graph` and `DiGraph` have in common: the nodes, the index of their names and the storage of
+// the edges. Everything that doesn't depend on which way the edges go is done here once, and both
+// graph types just pass it through
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem,
+};
+
+use crate::{
+    AddNodeError, EdgeEditError, Key, Node, NodeNameChangeError, NodeValueChangeError,
+    RemoveNodeError, Storage,
+};
+
+#[derive(Clone)]
+pub(crate) struct Base<T, K, E, S> {
+    pub(crate) nodes: Vec<Node<T, K>>,
+    pub(crate) edges: S,
+    /// Position of each node in `nodes`, by name
+    pub(crate) index: HashMap<K, usize>,
+    weight: PhantomData<E>,
+}
+
+// The index is left out: it's fully determined by the nodes
+impl<T, K, E, S> PartialEq for Base<T, K, E, S>
+where
+    Node<T, K>: PartialEq,
+    S: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.edges == other.edges
+    }
+}
+
+impl<T, K, E, S> Eq for Base<T, K, E, S>
+where
+    Node<T, K>: Eq,
+    S: Eq,
+{
+}
+
+impl<T, K, E, S> Base<T, K, E, S>
+where
+    Node<T, K>: Debug,
+    S: Debug,
+{
+    /// Debug output for the graph type called `name`
+    pub(crate) fn debug(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(name).field("nodes", &self.nodes).field("edges", &self.edges).finish()
+    }
+}
+
+impl<T, K: Key, E, S: Storage<E>> Base<T, K, E, S> {
+    /// Builds the name to position index. Assumes the names are unique
+    pub(crate) fn new(nodes: Vec<Node<T, K>>, edges: S) -> Self {
+        let index = nodes.iter().enumerate().map(|(i, n)| (n.name.clone(), i)).collect();
+        Base { nodes, edges, index, weight: PhantomData }
+    }
+
+    /// Same nodes, with the edges moved over to another kind of storage
+    pub(crate) fn into_storage<S2: Storage<E>>(mut self) -> Base<T, K, E, S2> {
+        let n = self.nodes.len();
+        let mut edges = S2::empty(n);
+        for a in 0..n {
+            let bs: Vec<usize> = self.edges.neighbours(a).map(|(b, _)| b).collect();
+            for b in bs {
+                edges.set(a, b, self.edges.set(a, b, None));
+            }
+        }
+        Base { nodes: self.nodes, edges, index: self.index, weight: PhantomData }
+    }
+
+    /// Position of the node in `nodes`, which is also its position in the storage
+    pub(crate) fn index_of(&self, x: &K) -> Option<usize> {
+        self.index.get(x).copied()
+    }
+
+    pub(crate) fn get_node(&self, x: K) -> Option<&Node<T, K>> {
+        Some(&self.nodes[self.index_of(&x)?])
+    }
+
+    pub(crate) fn change_node_name(
+        &mut self,
+        original: K,
+        new: K,
+    ) -> Result<&T, NodeNameChangeError> {
+        if self.index.contains_key(&new) {
+            return Err(NodeNameChangeError::NameAlreadyTaken);
+        }
+        if let Some(i) = self.index.remove(&original) {
+            self.index.insert(new.clone(), i);
+            let node = &mut self.nodes[i];
+            node.name = new;
+            Ok(&node.value)
+        } else {
+            Err(NodeNameChangeError::NodeNotFound)
+        }
+    }
+
+    pub(crate) fn change_node_value(
+        &mut self,
+        node: K,
+        mut new_val: T,
+    ) -> Result<T, NodeValueChangeError> {
+        if let Some(i) = self.index_of(&node) {
+            mem::swap(&mut new_val, &mut self.nodes[i].value);
+            Ok(new_val)
+        } else {
+            Err(NodeValueChangeError::NodeNotFound)
+        }
+    }
+
+    /// Whether the storage has a -> b
+    pub(crate) fn has_adjacency(&self, a: K, b: K) -> Option<bool> {
+        let a_idx = self.index_of(&a)?;
+        let b_idx = self.index_of(&b)?;
+        Some(self.edges.weight(a_idx, b_idx).is_some())
+    }
+
+    pub(crate) fn edge_weight(&self, a: K, b: K) -> Option<&E> {
+        let a_idx = self.index_of(&a)?;
+        let b_idx = self.index_of(&b)?;
+        self.edges.weight(a_idx, b_idx)
+    }
+
+    /// Nodes that c has an edge to
+    pub(crate) fn neighbours(&self, c: K) -> Option<Vec<&Node<T, K>>> {
+        let i = self.index_of(&c)?;
+        Some(self.edges.neighbours(i).map(|(j, _)| &self.nodes[j]).collect())
+    }
+
+    pub(crate) fn add_node(&mut self, node: Node<T, K>) -> Result<(), AddNodeError> {
+        if self.index.contains_key(&node.name) {
+            return Err(AddNodeError::NameAlreadyTaken);
+        }
+        self.index.insert(node.name.clone(), self.nodes.len());
+        self.nodes.push(node);
+        self.edges.push_node();
+        Ok(())
+    }
+
+    pub(crate) fn remove_node(&mut self, c: K) -> Result<Node<T, K>, RemoveNodeError> {
+        let i = self.index.remove(&c).ok_or(RemoveNodeError::NodeNotFound)?;
+        self.edges.remove_node(i);
+        // Everything after it moves back one position
+        for j in self.index.values_mut() {
+            if *j > i {
+                *j -= 1;
+            }
+        }
+        Ok(self.nodes.remove(i))
+    }
+
+    /// Positions of both ends of an edge that's about to be edited
+    pub(crate) fn edge_indices(&self, a: K, b: K) -> Result<(usize, usize), EdgeEditError> {
+        if a == b {
+            return Err(EdgeEditError::SelfReferentialNode);
+        }
+        let a_idx = self.index_of(&a).ok_or(EdgeEditError::NodeNotFound)?;
+        let b_idx = self.index_of(&b).ok_or(EdgeEditError::NodeNotFound)?;
+        Ok((a_idx, b_idx))
+    }
+}
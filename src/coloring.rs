@@ -0,0 +1,155 @@
+// Vertex colourings: adjacent nodes always get different colours. Colours are numbered from 0
+
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::{Graph, Key, Storage};
+
+#[derive(Debug, Error)]
+pub enum ColoringError {
+    #[error("a node in the given order was not found in the graph")]
+    NodeNotFound,
+}
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// Goes through the nodes in the given order, giving each one the lowest colour none of its
+    /// neighbours has. Nodes left out of the order go last, in the order of the graph, and repeated
+    /// ones only count the first time
+    pub fn greedy_coloring(
+        &self,
+        order: impl IntoIterator<Item = K>,
+    ) -> Result<HashMap<K, usize>, ColoringError> {
+        let n = self.base.nodes.len();
+        let mut positions = Vec::with_capacity(n);
+        for k in order {
+            positions.push(self.index_of(&k).ok_or(ColoringError::NodeNotFound)?);
+        }
+        positions.extend(0..n);
+
+        let mut colors: Vec<Option<usize>> = vec![None; n];
+        for x in positions {
+            if colors[x].is_none() {
+                colors[x] = Some(self.lowest_free_color(x, &colors));
+            }
+        }
+        Ok(self.color_map(&colors))
+    }
+
+    /// DSatur: colours next the node whose neighbours already have the most different colours
+    /// (ties go to the one with most neighbours), giving it the lowest colour they don't have.
+    /// Usually needs fewer colours than `greedy_coloring`
+    pub fn dsatur_coloring(&self) -> HashMap<K, usize> {
+        let n = self.base.nodes.len();
+        let mut colors: Vec<Option<usize>> = vec![None; n];
+        for _ in 0..n {
+            let x = self.most_saturated(&colors).expect("there are nodes left");
+            colors[x] = Some(self.lowest_free_color(x, &colors));
+        }
+        self.color_map(&colors)
+    }
+
+    /// A colouring with as few colours as possible. It's found by backtracking, which takes
+    /// exponential time in the worst case: only meant for small graphs
+    pub fn minimum_coloring(&self) -> HashMap<K, usize> {
+        let n = self.base.nodes.len();
+
+        // DSatur's colouring is the one to beat, and a clique needs a colour for each node
+        let dsatur = self.dsatur_coloring();
+        let initial: Vec<usize> = self.base.nodes.iter().map(|node| dsatur[&node.name]).collect();
+        let mut best = Best { count: initial.iter().max().map_or(0, |m| m + 1), colors: initial };
+        let lower = self.greedy_clique_size();
+
+        let mut colors = vec![None; n];
+        self.color_search(&mut colors, 0, lower, &mut best);
+        self.color_map(&best.colors.into_iter().map(Some).collect::<Vec<_>>())
+    }
+
+    /// Least amount of colours a colouring needs, see `minimum_coloring`
+    pub fn chromatic_number(&self) -> usize {
+        self.minimum_coloring().values().max().map_or(0, |m| m + 1)
+    }
+
+    /// Tries every colour that could lead to something better than `best` for the most saturated
+    /// node, then goes on to the next one. `used` is the amount of colours in `colors` so far
+    fn color_search(
+        &self,
+        colors: &mut [Option<usize>],
+        used: usize,
+        lower: usize,
+        best: &mut Best,
+    ) {
+        if best.count <= lower {
+            return;
+        }
+        let Some(x) = self.most_saturated(colors) else {
+            // Everything is coloured, and with fewer colours than before or it'd have been cut off
+            best.count = used;
+            best.colors = colors.iter().map(|c| c.unwrap()).collect();
+            return;
+        };
+
+        let taken = self.neighbour_colors(x, colors);
+        // A brand new colour only makes sense once, and only if it still beats `best`
+        let limit = (used + 1).min(best.count - 1);
+        for c in 0..limit {
+            if taken.contains(&c) { continue; }
+            colors[x] = Some(c);
+            self.color_search(colors, used.max(c + 1), lower, best);
+            colors[x] = None;
+            if best.count <= lower { return; }
+        }
+    }
+
+    /// Uncoloured node with most differently coloured neighbours, then most neighbours, then first
+    /// in the graph. None if they're all coloured
+    fn most_saturated(&self, colors: &[Option<usize>]) -> Option<usize> {
+        (0..self.base.nodes.len())
+            .filter(|&x| colors[x].is_none())
+            .map(|x| {
+                let seen = self.neighbour_colors(x, colors);
+                (seen.len(), self.base.edges.neighbours(x).count(), x)
+            })
+            .max_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(b.2.cmp(&a.2)))
+            .map(|(_, _, x)| x)
+    }
+
+    fn lowest_free_color(&self, x: usize, colors: &[Option<usize>]) -> usize {
+        let taken = self.neighbour_colors(x, colors);
+        (0..).find(|c| !taken.contains(c)).unwrap()
+    }
+
+    fn neighbour_colors(&self, x: usize, colors: &[Option<usize>]) -> HashSet<usize> {
+        self.base.edges.neighbours(x).filter_map(|(y, _)| colors[y]).collect()
+    }
+
+    /// Size of a clique found by taking nodes from most to fewest neighbours, whenever they're
+    /// adjacent to all the ones taken so far
+    fn greedy_clique_size(&self) -> usize {
+        let mut order: Vec<usize> = (0..self.base.nodes.len()).collect();
+        order.sort_by_key(|&x| std::cmp::Reverse(self.base.edges.neighbours(x).count()));
+
+        let mut clique: Vec<usize> = Vec::new();
+        for x in order {
+            if clique.iter().all(|&y| self.base.edges.weight(x, y).is_some()) {
+                clique.push(x);
+            }
+        }
+        clique.len()
+    }
+
+    fn color_map(&self, colors: &[Option<usize>]) -> HashMap<K, usize> {
+        self.base
+            .nodes
+            .iter()
+            .zip(colors)
+            .map(|(node, c)| (node.name.clone(), c.expect("every node is coloured")))
+            .collect()
+    }
+}
+
+/// Best colouring found so far, by position
+struct Best {
+    count: usize,
+    colors: Vec<usize>,
+}
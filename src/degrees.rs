@@ -0,0 +1,116 @@
+// Degrees of the nodes of a graph, and which lists of degrees a graph can have
+
+use thiserror::Error;
+
+use crate::{base::Base, utils::has_duplicates, AdjMatrix, Graph, Key, Node, Storage};
+
+#[derive(Error, Debug)]
+pub enum FromDegreeSequenceError {
+    #[error("the names of the nodes contain duplicates")]
+    NodesArentUnique,
+    #[error("there must be exactly one degree per node")]
+    WrongLength,
+    #[error("no simple graph has these degrees")]
+    NotGraphical,
+}
+
+impl<T, K: Key> Graph<T, K> {
+    /// A graph whose nodes have the given degrees, the i-th node having the i-th degree. It's built
+    /// with Havel–Hakimi: the node that needs the most edges takes them from the ones that need
+    /// the most after it, and so on until none need any more
+    pub fn from_degree_sequence(
+        nodes: Vec<Node<T, K>>,
+        degrees: &[usize],
+    ) -> Result<Self, FromDegreeSequenceError> {
+        if nodes.len() != degrees.len() {
+            return Err(FromDegreeSequenceError::WrongLength);
+        }
+        if has_duplicates(nodes.iter().map(|n| &n.name)) {
+            return Err(FromDegreeSequenceError::NodesArentUnique);
+        }
+
+        let n = nodes.len();
+        let mut g = Graph { base: Base::new(nodes, AdjMatrix::empty(n)) };
+        // How many edges each node still needs, by position
+        let mut missing: Vec<(usize, usize)> = degrees.iter().copied().zip(0..n).collect();
+        loop {
+            missing.sort_unstable_by(|a, b| b.cmp(a));
+            let Some(&(d, x)) = missing.first() else { break };
+            if d == 0 { break; }
+            if d >= missing.len() {
+                return Err(FromDegreeSequenceError::NotGraphical);
+            }
+
+            missing[0].0 = 0;
+            for (dy, y) in &mut missing[1..=d] {
+                if *dy == 0 {
+                    return Err(FromDegreeSequenceError::NotGraphical);
+                }
+                *dy -= 1;
+                g.set_edge(x, *y, Some(()));
+            }
+        }
+
+        Ok(g)
+    }
+}
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// Degrees of all nodes, from highest to lowest
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = self.degrees();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+
+    /// None if there are no nodes
+    pub fn min_degree(&self) -> Option<usize> {
+        self.degrees().into_iter().min()
+    }
+
+    /// None if there are no nodes
+    pub fn max_degree(&self) -> Option<usize> {
+        self.degrees().into_iter().max()
+    }
+
+    /// Whether all nodes have the same degree. The empty graph counts as regular
+    pub fn is_regular(&self) -> bool {
+        self.min_degree() == self.max_degree()
+    }
+
+    /// Position d holds how many nodes have degree d, up to the highest degree
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        let degrees = self.degrees();
+        let mut histogram = vec![0; degrees.iter().max().map_or(0, |m| m + 1)];
+        for d in degrees {
+            histogram[d] += 1;
+        }
+        histogram
+    }
+
+    /// Degree of each node, by position
+    fn degrees(&self) -> Vec<usize> {
+        (0..self.base.nodes.len()).map(|x| self.base.edges.neighbours(x).count()).collect()
+    }
+}
+
+/// Whether some simple graph has these degrees, in any order. Uses the Erdős–Gallai theorem: with
+/// the degrees sorted from highest to lowest, their sum is even and, for every k, the first k
+/// degrees add up to at most k(k - 1) + the sum of min(d, k) over the rest
+pub fn is_graphical(degrees: &[usize]) -> bool {
+    let mut d = degrees.to_vec();
+    d.sort_unstable_by(|a, b| b.cmp(a));
+    if d.iter().sum::<usize>() % 2 == 1 {
+        return false;
+    }
+
+    let mut left = 0;
+    for k in 1..=d.len() {
+        left += d[k - 1];
+        let right = k * (k - 1) + d[k..].iter().map(|&x| x.min(k)).sum::<usize>();
+        if left > right {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,317 @@
+// Directed graphs: same nodes and constructors as `Graph`, but edges go one way only
+
+use std::{
+    fmt::{self, Debug},
+    hash::Hash,
+};
+
+use crate::{
+    base::Base,
+    storage,
+    utils::{dedup, has_duplicates},
+    AddNodeError, AdjList, AdjMatrix, AdjacencyLists, EdgeEditError, FromListError, FromMatrixError,
+    Graph, Key, Node, NodeNameChangeError, NodeValueChangeError, RemoveNodeError, Storage,
+    WeightedAdjacencyLists,
+};
+
+/// Directed graph. Its storage doesn't need to be symmetric: the edge a -> b says nothing about
+/// b -> a. Nodes still may not point to themselves
+#[derive(Clone)]
+pub struct DiGraph<T, K = char, E = (), S = AdjMatrix<E>> {
+    pub(crate) base: Base<T, K, E, S>,
+}
+
+impl<T, K, E, S> PartialEq for DiGraph<T, K, E, S>
+where
+    Node<T, K>: PartialEq,
+    S: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+    }
+}
+
+impl<T, K, E, S> Eq for DiGraph<T, K, E, S>
+where
+    Node<T, K>: Eq,
+    S: Eq,
+{
+}
+
+impl<T, K, E, S> Debug for DiGraph<T, K, E, S>
+where
+    Node<T, K>: Debug,
+    S: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.base.debug("DiGraph", f)
+    }
+}
+
+/// Same as `DiGraph`, but with sparse storage. Meant for big graphs with few edges per node: build
+/// one with the `_in` constructors or `SparseDiGraph::empty()`, or turn a graph into one with
+/// `into_storage`
+pub type SparseDiGraph<T, K = char, E = ()> = DiGraph<T, K, E, AdjList<E>>;
+
+impl<T, K: Key> DiGraph<T, K> {
+    /// Errors if the main diagonal has at least one true, or if one of the names is repeated
+    pub fn from_matrix<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[bool; N]; N],
+    ) -> Result<Self, FromMatrixError> {
+        Self::from_matrix_in(names, values, m)
+    }
+
+    /// Each node along with the nodes it points to. Nodes that don't point anywhere may be left
+    /// out of the main positions
+    pub fn from_list(lists: AdjacencyLists<T, K>) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+    {
+        Self::from_list_in(lists)
+    }
+}
+
+impl<T, K: Key, S: Storage<()>> DiGraph<T, K, (), S> {
+    /// Same as `from_list`, but with the storage of the graph type: `SparseDiGraph::from_list_in`
+    /// never goes through a matrix
+    pub fn from_list_in(lists: AdjacencyLists<T, K>) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+    {
+        Self::from_weighted_list_in(
+            lists
+                .into_iter()
+                .map(|(main, adjs)| (main, adjs.into_iter().map(|n| (n, ())).collect()))
+                .collect(),
+        )
+    }
+
+    /// Same as `from_matrix`, but with the storage of the graph type
+    pub fn from_matrix_in<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[bool; N]; N],
+    ) -> Result<Self, FromMatrixError> {
+        Self::from_weighted_matrix_in(names, values, m.map(|row| row.map(|b| b.then_some(()))))
+    }
+
+    /// Makes a -> b.
+    ///
+    /// Returns whether the edge is new (false means it was already there)
+    pub fn add_edge(&mut self, a: K, b: K) -> Result<bool, EdgeEditError> {
+        Ok(self.add_weighted_edge(a, b, ())?.is_none())
+    }
+}
+
+impl<T, K: Key, E> DiGraph<T, K, E> {
+    /// Same as `from_matrix`, but each `Some` holds the value of its edge
+    pub fn from_weighted_matrix<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[Option<E>; N]; N],
+    ) -> Result<Self, FromMatrixError> {
+        Self::from_weighted_matrix_in(names, values, m)
+    }
+
+    /// Same as `from_list`, but each successor comes with the value of the edge. If an edge is
+    /// listed more than once, it must always have the same value
+    pub fn from_weighted_list(lists: WeightedAdjacencyLists<T, K, E>) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+        E: PartialEq,
+    {
+        Self::from_weighted_list_in(lists)
+    }
+}
+
+impl<T, K: Key, E, S: Storage<E>> DiGraph<T, K, E, S> {
+    /// A graph without nodes
+    pub fn empty() -> Self {
+        DiGraph { base: Base::new(Vec::new(), S::empty(0)) }
+    }
+
+    /// Same as `from_weighted_matrix`, but with the storage of the graph type
+    pub fn from_weighted_matrix_in<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[Option<E>; N]; N],
+    ) -> Result<Self, FromMatrixError> {
+        if has_duplicates(&names) {
+            return Err(FromMatrixError::NodesArentUnique);
+        }
+        let nodes = names.into_iter().zip(values).map(|(c, v)| Node::new(c, v)).collect();
+        let edges = storage::from_matrix(m)?;
+
+        Ok(DiGraph { base: Base::new(nodes, edges) })
+    }
+
+    /// Same as `from_weighted_list`, but with the storage of the graph type
+    pub fn from_weighted_list_in(
+        lists: WeightedAdjacencyLists<T, K, E>,
+    ) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+        E: PartialEq,
+    {
+        // Same order as `Graph::from_list`: each main node followed by its neighbours
+        let mut nodes = Vec::new();
+        let mut arcs = Vec::new();
+        for (main, adjs) in lists {
+            let name = main.name.clone();
+            nodes.push(main);
+            for (n, w) in adjs {
+                arcs.push((name.clone(), n.name.clone(), w));
+                nodes.push(n);
+            }
+        }
+
+        dedup(&mut nodes);
+
+        if has_duplicates(nodes.iter().map(|n| &n.name)) {
+            return Err(FromListError::NodesArentUnique);
+        }
+
+        let n = nodes.len();
+        let mut base = Base::new(nodes, S::empty(n));
+        for (a, b, w) in arcs {
+            if a == b {
+                return Err(FromListError::SelfReferentialNode);
+            }
+            let (a, b) = (base.index[&a], base.index[&b]);
+            if base.edges.weight(a, b).is_some_and(|old| *old != w) {
+                return Err(FromListError::MalformedLists);
+            }
+            base.edges.set(a, b, Some(w));
+        }
+
+        Ok(DiGraph { base })
+    }
+
+    /// Same graph, with its edges kept in another kind of storage
+    pub fn into_storage<S2: Storage<E>>(self) -> DiGraph<T, K, E, S2> {
+        DiGraph { base: self.base.into_storage() }
+    }
+
+    fn index_of(&self, x: &K) -> Option<usize> {
+        self.base.index_of(x)
+    }
+
+    /// Every node, in the order the graph keeps them. Results given as matrices, like
+    /// `floyd_warshall`'s, use this same order
+    pub fn nodes(&self) -> &[Node<T, K>] {
+        &self.base.nodes
+    }
+
+    pub fn get_node(&self, x: K) -> Option<&Node<T, K>> {
+        self.base.get_node(x)
+    }
+
+    /// Same as `Graph::change_node_name`
+    pub fn change_node_name(&mut self, original: K, new: K) -> Result<&T, NodeNameChangeError> {
+        self.base.change_node_name(original, new)
+    }
+
+    /// Same as `Graph::change_node_value`
+    pub fn change_node_value(&mut self, node: K, new_val: T) -> Result<T, NodeValueChangeError> {
+        self.base.change_node_value(node, new_val)
+    }
+
+    /// Adds an isolated node
+    ///
+    /// Errors if its name is already taken
+    pub fn add_node(&mut self, node: Node<T, K>) -> Result<(), AddNodeError> {
+        self.base.add_node(node)
+    }
+
+    /// Removes a node along with every edge going into it or out of it.
+    ///
+    /// Returns the removed node
+    pub fn remove_node(&mut self, c: K) -> Result<Node<T, K>, RemoveNodeError> {
+        self.base.remove_node(c)
+    }
+
+    /// Makes a -> b, with `weight` as the value of the edge.
+    ///
+    /// Returns the previous value if the edge was already there
+    pub fn add_weighted_edge(&mut self, a: K, b: K, weight: E) -> Result<Option<E>, EdgeEditError> {
+        let (a_idx, b_idx) = self.base.edge_indices(a, b)?;
+        Ok(self.base.edges.set(a_idx, b_idx, Some(weight)))
+    }
+
+    /// Removes a -> b, leaving b -> a alone.
+    ///
+    /// Returns whether there was an edge to remove
+    pub fn remove_edge(&mut self, a: K, b: K) -> Result<bool, EdgeEditError> {
+        let (a_idx, b_idx) = self.base.edge_indices(a, b)?;
+        Ok(self.base.edges.set(a_idx, b_idx, None).is_some())
+    }
+
+    /// Whether a -> b. Returns None if either or both of the nodes do not exist in the graph
+    pub fn has_adjacency(&self, a: K, b: K) -> Option<bool> {
+        self.base.has_adjacency(a, b)
+    }
+
+    /// Value of the edge a -> b. Returns None if either or both of the nodes do not exist in the
+    /// graph, or if there's no such edge
+    pub fn edge_weight(&self, a: K, b: K) -> Option<&E> {
+        self.base.edge_weight(a, b)
+    }
+
+    /// Nodes that c points to
+    pub fn successors(&self, c: K) -> Option<Vec<&Node<T, K>>> {
+        self.base.neighbours(c)
+    }
+
+    /// Nodes that point to c
+    pub fn predecessors(&self, c: K) -> Option<Vec<&Node<T, K>>> {
+        let i = self.index_of(&c)?;
+        Some(
+            self.base
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| self.base.edges.weight(*j, i).is_some())
+                .map(|(_, n)| n)
+                .collect(),
+        )
+    }
+
+    pub fn out_degree(&self, c: K) -> Option<usize> {
+        let i = self.index_of(&c)?;
+        Some(self.base.edges.neighbours(i).count())
+    }
+
+    pub fn in_degree(&self, c: K) -> Option<usize> {
+        self.predecessors(c).map(|v| v.len())
+    }
+
+    /// The underlying undirected graph: a ~ b if a -> b or b -> a. When both edges exist and carry
+    /// different values, the one leaving the node that was added first is kept
+    pub fn into_undirected(mut self) -> Graph<T, K, E, S>
+    where
+        E: Clone,
+    {
+        let mut missing = Vec::new();
+        for a in 0..self.base.nodes.len() {
+            for (b, w) in self.base.edges.neighbours(a) {
+                if a < b || self.base.edges.weight(b, a).is_none() {
+                    missing.push((b, a, w.clone()));
+                }
+            }
+        }
+        for (b, a, w) in missing {
+            self.base.edges.set(b, a, Some(w));
+        }
+
+        Graph { base: self.base }
+    }
+}
+
+/// Every undirected edge becomes two edges, one each way
+impl<T, K, E, S> From<Graph<T, K, E, S>> for DiGraph<T, K, E, S> {
+    fn from(g: Graph<T, K, E, S>) -> Self {
+        DiGraph { base: g.base }
+    }
+}
@@ -0,0 +1,289 @@
+// Rasterises a graph to a PPM image: edges are straight lines and nodes are discs with their
+// name written on top
+
+use std::{collections::HashMap, f64::consts::PI, fmt::Display, fs, io, path::Path};
+
+use thiserror::Error;
+
+use crate::{utils::coords_to_idx, Graph, Key, Storage};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0);
+    pub const WHITE: Color = Color::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// Where each node's centre goes, in pixels from the top left corner
+#[derive(Clone, Debug, Default)]
+pub enum Layout<K = char> {
+    /// Evenly spread around a circle, in the order the graph stores its nodes
+    #[default]
+    Circle,
+    /// Given by the caller. Every node of the graph must be present
+    Manual(HashMap<K, (usize, usize)>),
+}
+
+#[derive(Clone, Debug)]
+pub struct DrawOptions<K = char> {
+    pub width: usize,
+    pub height: usize,
+    pub layout: Layout<K>,
+    /// In pixels
+    pub node_radius: usize,
+    pub background: Color,
+    pub node_color: Color,
+    pub edge_color: Color,
+    pub label_color: Color,
+}
+
+impl<K> Default for DrawOptions<K> {
+    fn default() -> Self {
+        Self {
+            width: 512,
+            height: 512,
+            layout: Layout::Circle,
+            node_radius: 16,
+            background: Color::WHITE,
+            node_color: Color::new(70, 130, 180),
+            edge_color: Color::BLACK,
+            label_color: Color::WHITE,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DrawError {
+    #[error("node '{0}' has no position in the manual layout")]
+    MissingPosition(String),
+    #[error("the image must be at least 1x1")]
+    EmptyImage,
+    #[error("could not write the image: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Plain RGB image, stored row by row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Self { width, height, pixels: vec![background; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns None if (x, y) is out of bounds
+    pub fn get(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            None
+        } else {
+            Some(self.pixels[coords_to_idx(x, y, self.width)])
+        }
+    }
+
+    /// Out of bounds pixels are silently ignored, so shapes may stick out of the image
+    fn set(&mut self, x: i64, y: i64, c: Color) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            let i = coords_to_idx(x as usize, y as usize, self.width);
+            self.pixels[i] = c;
+        }
+    }
+
+    /// Bresenham's
+    fn line(&mut self, (mut x0, mut y0): (i64, i64), (x1, y1): (i64, i64), c: Color) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            self.set(x0, y0, c);
+            if x0 == x1 && y0 == y1 { break; }
+            let e2 = 2 * err;
+            if e2 >= dy { err += dy; x0 += sx; }
+            if e2 <= dx { err += dx; y0 += sy; }
+        }
+    }
+
+    fn disc(&mut self, (cx, cy): (i64, i64), r: i64, c: Color) {
+        for y in -r..=r {
+            for x in -r..=r {
+                if x * x + y * y <= r * r {
+                    self.set(cx + x, cy + y, c);
+                }
+            }
+        }
+    }
+
+    /// Writes `text` centered on (cx, cy), each font pixel being a `scale`x`scale` square. There's
+    /// a blank font column between letters
+    fn text(&mut self, (cx, cy): (i64, i64), text: &str, scale: i64, c: Color) {
+        let len = text.chars().count() as i64;
+        let left = cx - (len * (GLYPH_W + 1) - 1) * scale / 2;
+        let top = cy - GLYPH_H * scale / 2;
+
+        for (k, ch) in text.chars().enumerate() {
+            let origin = left + k as i64 * (GLYPH_W + 1) * scale;
+            for (j, row) in glyph_rows(ch).iter().enumerate() {
+                for i in 0..GLYPH_W {
+                    if row & (1 << (GLYPH_W - 1 - i)) == 0 { continue; }
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            self.set(origin + i * scale + dx, top + j as i64 * scale + dy, c);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Binary PPM (P6)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.reserve(self.pixels.len() * 3);
+        for p in &self.pixels {
+            out.extend([p.r, p.g, p.b]);
+        }
+        out
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), DrawError> {
+        fs::write(path, self.to_ppm())?;
+        Ok(())
+    }
+}
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// Draws every edge as a line and every node as a disc labelled with its name
+    pub fn draw(&self, options: &DrawOptions<K>) -> Result<Image, DrawError>
+    where
+        K: Display,
+    {
+        if options.width == 0 || options.height == 0 {
+            return Err(DrawError::EmptyImage);
+        }
+
+        let positions: Vec<(i64, i64)> = match &options.layout {
+            Layout::Circle => circle_layout(self.base.nodes.len(), options),
+            Layout::Manual(ps) => self
+                .base
+                .nodes
+                .iter()
+                .map(|n| {
+                    let (x, y) = ps.get(&n.name).ok_or_else(|| DrawError::MissingPosition(n.name.to_string()))?;
+                    Ok((*x as i64, *y as i64))
+                })
+                .collect::<Result<_, DrawError>>()?,
+        };
+
+        let mut img = Image::new(options.width, options.height, options.background);
+
+        for i in 0..self.base.nodes.len() {
+            for (j, _) in self.base.edges.neighbours(i).filter(|(j, _)| *j > i) {
+                img.line(positions[i], positions[j], options.edge_color);
+            }
+        }
+
+        let r = options.node_radius as i64;
+        for (n, p) in self.base.nodes.iter().zip(&positions) {
+            let label = n.name.to_string();
+            // The label should fit comfortably inside the disc
+            let len = label.chars().count().max(1) as i64;
+            let scale = (r / GLYPH_H).min(2 * r / (len * (GLYPH_W + 1))).max(1);
+
+            img.disc(*p, r, options.node_color);
+            img.text(*p, &label, scale, options.label_color);
+        }
+
+        Ok(img)
+    }
+
+    /// Convenience for `draw` followed by `Image::save`
+    pub fn draw_to_file(&self, path: impl AsRef<Path>, options: &DrawOptions<K>) -> Result<(), DrawError>
+    where
+        K: Display,
+    {
+        self.draw(options)?.save(path)
+    }
+}
+
+fn circle_layout<K>(n: usize, options: &DrawOptions<K>) -> Vec<(i64, i64)> {
+    let (cx, cy) = (options.width as f64 / 2.0, options.height as f64 / 2.0);
+    let radius = (cx.min(cy) - options.node_radius as f64 - 1.0).max(0.0);
+
+    (0..n)
+        .map(|i| {
+            // Start at the top and go clockwise
+            let angle = 2.0 * PI * i as f64 / n as f64 - PI / 2.0;
+            ((cx + radius * angle.cos()).round() as i64, (cy + radius * angle.sin()).round() as i64)
+        })
+        .collect()
+}
+
+const GLYPH_W: i64 = 5;
+const GLYPH_H: i64 = 7;
+
+/// 5x7 bitmap font, one row per element with the leftmost pixel as the highest bit. Lowercase
+/// letters are drawn as uppercase and anything unknown becomes a hollow box
+fn glyph_rows(ch: char) -> [u8; GLYPH_H as usize] {
+    match ch.to_ascii_uppercase() {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        _ => [0b11111, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11111],
+    }
+}
@@ -0,0 +1,97 @@
+// Eulerian paths and circuits: walks that go through every edge exactly once
+
+use crate::{Edge, Graph, Key, Storage};
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// Whether there's a closed walk that goes through every edge once. That's the case when every
+    /// node has even degree and all the edges are in the same component
+    pub fn is_eulerian(&self) -> bool {
+        self.odd_nodes().is_empty() && self.edges_are_connected()
+    }
+
+    /// Whether there's a walk, closed or not, that goes through every edge once. That's the case
+    /// when none or two of the nodes have odd degree and all the edges are in the same component
+    pub fn has_eulerian_path(&self) -> bool {
+        self.odd_nodes().len() <= 2 && self.edges_are_connected()
+    }
+
+    /// A closed walk going through every edge once, as a list of edges each starting where the
+    /// previous one ended. None if there's no such walk, and empty if there are no edges
+    pub fn eulerian_circuit(&self) -> Option<Vec<Edge<'_, T, K>>> {
+        if !self.is_eulerian() {
+            return None;
+        }
+        let start =
+            (0..self.base.nodes.len()).find(|&x| self.base.edges.neighbours(x).next().is_some());
+        Some(start.map(|s| self.hierholzer(s)).unwrap_or_default())
+    }
+
+    /// A walk going through every edge once, same as `eulerian_circuit`. If two nodes have odd
+    /// degree it goes from one to the other, otherwise it's closed
+    pub fn eulerian_path(&self) -> Option<Vec<Edge<'_, T, K>>> {
+        if !self.has_eulerian_path() {
+            return None;
+        }
+        match self.odd_nodes().first() {
+            Some(&s) => Some(self.hierholzer(s)),
+            None => self.eulerian_circuit(),
+        }
+    }
+
+    fn odd_nodes(&self) -> Vec<usize> {
+        (0..self.base.nodes.len())
+            .filter(|&x| self.base.edges.neighbours(x).count() % 2 == 1)
+            .collect()
+    }
+
+    /// Whether all the nodes that have edges are in the same component
+    fn edges_are_connected(&self) -> bool {
+        let components = self.components();
+        components
+            .iter()
+            .filter(|c| c.iter().any(|&x| self.base.edges.neighbours(x).next().is_some()))
+            .count()
+            <= 1
+    }
+
+    /// Hierholzer's algorithm. Walks from `start` until getting stuck, which can only happen back
+    /// at a node with odd degree (or `start` itself), then backtracks until finding a node with
+    /// unused edges and walks from there too, splicing that detour in
+    fn hierholzer(&self, start: usize) -> Vec<Edge<'_, T, K>> {
+        let n = self.base.nodes.len();
+
+        // Each edge gets a number, so that using it from one end uses it from the other too
+        let mut lists: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+        let mut count = 0;
+        for a in 0..n {
+            for (b, _) in self.base.edges.neighbours(a) {
+                if a < b {
+                    lists[a].push((b, count));
+                    lists[b].push((a, count));
+                    count += 1;
+                }
+            }
+        }
+        let mut used = vec![false; count];
+        // How far along its list each node has looked, everything before is used
+        let mut next = vec![0; n];
+
+        let mut stack = vec![start];
+        let mut walk = Vec::with_capacity(count + 1);
+        while let Some(&x) = stack.last() {
+            while next[x] < lists[x].len() && used[lists[x][next[x]].1] {
+                next[x] += 1;
+            }
+            match lists[x].get(next[x]) {
+                Some(&(y, e)) => {
+                    used[e] = true;
+                    stack.push(y);
+                }
+                None => walk.push(stack.pop().unwrap()),
+            }
+        }
+
+        walk.reverse();
+        walk.windows(2).map(|w| (&self.base.nodes[w[0]], &self.base.nodes[w[1]])).collect()
+    }
+}
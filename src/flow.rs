@@ -0,0 +1,267 @@
+// Maximum flows and minimum cuts, taking the value of each edge as its capacity
+
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::Sub,
+};
+
+use thiserror::Error;
+
+use crate::{base::Base, DiGraph, Graph, Key, Node, Storage, Weight};
+
+#[derive(Debug, Error)]
+pub enum MaxFlowError {
+    #[error("source vertex was not found in the graph")]
+    SourceNotFound,
+    #[error("sink vertex was not found in the graph")]
+    SinkNotFound,
+    #[error("source and sink are the same vertex")]
+    SourceIsSink,
+    #[error("found an edge with negative capacity")]
+    NegativeCapacity,
+}
+
+/// An edge and how much goes through it
+pub type EdgeFlow<'a, T, K = char, E = ()> = (&'a Node<T, K>, &'a Node<T, K>, E);
+
+/// A maximum flow from source to sink, along with a minimum cut
+#[derive(Debug, Clone)]
+pub struct MaxFlow<'a, T, K = char, E = ()> {
+    /// Total amount that leaves the source, which is also the capacity of the minimum cut
+    pub value: E,
+    /// How much goes through each edge, leaving out the ones without any. Edges are directed: an
+    /// undirected edge shows up at most once, going the way its flow goes
+    pub flows: Vec<EdgeFlow<'a, T, K, E>>,
+    /// Nodes that can still be reached from the source through edges with capacity to spare. The
+    /// edges leaving them make up a minimum cut
+    pub source_side: Vec<&'a Node<T, K>>,
+}
+
+impl<T, K: Key, E: Weight + Sub<Output = E>, S: Storage<E>> Graph<T, K, E, S> {
+    /// Maximum flow from source to sink, every edge carrying at most its value in either direction
+    /// (it counts as one edge each way with that capacity). Same as `dinic`
+    pub fn max_flow(&self, source: K, sink: K) -> Result<MaxFlow<'_, T, K, E>, MaxFlowError> {
+        self.dinic(source, sink)
+    }
+
+    /// `max_flow` with Edmonds–Karp: augment along shortest paths, one at a time
+    pub fn edmonds_karp(&self, source: K, sink: K) -> Result<MaxFlow<'_, T, K, E>, MaxFlowError> {
+        self.base.max_flow(source, sink, Network::edmonds_karp, true)
+    }
+
+    /// `max_flow` with Dinic: augment along every shortest path at once, as a blocking flow
+    pub fn dinic(&self, source: K, sink: K) -> Result<MaxFlow<'_, T, K, E>, MaxFlowError> {
+        self.base.max_flow(source, sink, Network::dinic, true)
+    }
+}
+
+impl<T, K: Key, E: Weight + Sub<Output = E>, S: Storage<E>> DiGraph<T, K, E, S> {
+    /// Maximum flow from source to sink following edges forwards, every edge carrying at most its
+    /// value. Same as `dinic`
+    pub fn max_flow(&self, source: K, sink: K) -> Result<MaxFlow<'_, T, K, E>, MaxFlowError> {
+        self.dinic(source, sink)
+    }
+
+    /// `max_flow` with Edmonds–Karp: augment along shortest paths, one at a time
+    pub fn edmonds_karp(&self, source: K, sink: K) -> Result<MaxFlow<'_, T, K, E>, MaxFlowError> {
+        self.base.max_flow(source, sink, Network::edmonds_karp, false)
+    }
+
+    /// `max_flow` with Dinic: augment along every shortest path at once, as a blocking flow
+    pub fn dinic(&self, source: K, sink: K) -> Result<MaxFlow<'_, T, K, E>, MaxFlowError> {
+        self.base.max_flow(source, sink, Network::dinic, false)
+    }
+}
+
+/// Residual network. Every edge of the graph becomes an arc and a reverse arc with no capacity,
+/// next to each other: arc i's reverse is i ^ 1
+struct Network<E> {
+    heads: Vec<usize>,
+    /// Capacity left on each arc
+    residual: Vec<E>,
+    /// Arcs leaving each node
+    arcs: Vec<Vec<usize>>,
+}
+
+impl<E: Weight + Sub<Output = E>> Network<E> {
+    fn new<S: Storage<E>>(edges: &S) -> Result<Self, MaxFlowError> {
+        let n = edges.order();
+        let mut net = Network { heads: Vec::new(), residual: Vec::new(), arcs: vec![Vec::new(); n] };
+        for a in 0..n {
+            for (b, &c) in edges.neighbours(a) {
+                if c < E::default() {
+                    return Err(MaxFlowError::NegativeCapacity);
+                }
+                net.arcs[a].push(net.heads.len());
+                net.heads.push(b);
+                net.residual.push(c);
+                net.arcs[b].push(net.heads.len());
+                net.heads.push(a);
+                net.residual.push(E::default());
+            }
+        }
+        Ok(net)
+    }
+
+    fn push(&mut self, arc: usize, amount: E) {
+        self.residual[arc] = self.residual[arc] - amount;
+        self.residual[arc ^ 1] = self.residual[arc ^ 1] + amount;
+    }
+
+    /// BFS distance from s to every node through arcs with capacity left, None if unreachable
+    fn levels(&self, s: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.arcs.len()];
+        levels[s] = Some(0);
+        let mut queue = VecDeque::from([s]);
+        while let Some(x) = queue.pop_front() {
+            for &arc in &self.arcs[x] {
+                let y = self.heads[arc];
+                if levels[y].is_none() && self.residual[arc] > E::default() {
+                    levels[y] = Some(levels[x].unwrap() + 1);
+                    queue.push_back(y);
+                }
+            }
+        }
+        levels
+    }
+
+    fn edmonds_karp(&mut self, s: usize, t: usize) -> E {
+        let mut total = E::default();
+        loop {
+            // Arc used to reach each node, which makes for a shortest path back to s
+            let mut through: Vec<Option<usize>> = vec![None; self.arcs.len()];
+            let mut queue = VecDeque::from([s]);
+            while let Some(x) = queue.pop_front() {
+                for &arc in &self.arcs[x] {
+                    let y = self.heads[arc];
+                    if y != s && through[y].is_none() && self.residual[arc] > E::default() {
+                        through[y] = Some(arc);
+                        queue.push_back(y);
+                    }
+                }
+            }
+            if through[t].is_none() {
+                return total;
+            }
+
+            let mut path = Vec::new();
+            let mut x = t;
+            while let Some(arc) = through[x] {
+                path.push(arc);
+                x = self.heads[arc ^ 1];
+            }
+            let amount = self.bottleneck(&path);
+            for arc in path {
+                self.push(arc, amount);
+            }
+            total = total + amount;
+        }
+    }
+
+    fn dinic(&mut self, s: usize, t: usize) -> E {
+        let mut total = E::default();
+        loop {
+            let mut levels = self.levels(s);
+            if levels[t].is_none() {
+                return total;
+            }
+
+            // Arcs of each node that haven't been ruled out for this phase yet
+            let mut next = vec![0; self.arcs.len()];
+            // Current path from s, as arcs. Its end is where the search goes on from
+            let mut path: Vec<usize> = Vec::new();
+            loop {
+                let x = path.last().map_or(s, |&arc| self.heads[arc]);
+                if x == t {
+                    let amount = self.bottleneck(&path);
+                    for &arc in &path {
+                        self.push(arc, amount);
+                    }
+                    total = total + amount;
+                    // Back to just before the first arc that's now full
+                    let full = path.iter().position(|&arc| self.residual[arc] <= E::default());
+                    path.truncate(full.unwrap_or(0));
+                    continue;
+                }
+
+                let forward = self.arcs[x][next[x]..].iter().position(|&arc| {
+                    let y = self.heads[arc];
+                    self.residual[arc] > E::default()
+                        && levels[y].is_some()
+                        && levels[y] == levels[x].map(|l| l + 1)
+                });
+                match forward {
+                    Some(offset) => {
+                        next[x] += offset;
+                        path.push(self.arcs[x][next[x]]);
+                    }
+                    // Dead end, nothing goes through x anymore this phase
+                    None if x == s => break,
+                    None => {
+                        levels[x] = None;
+                        path.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    fn bottleneck(&self, path: &[usize]) -> E {
+        let mut amount = self.residual[path[0]];
+        for &arc in path {
+            if self.residual[arc] < amount {
+                amount = self.residual[arc];
+            }
+        }
+        amount
+    }
+}
+
+impl<T, K: Key, E: Weight + Sub<Output = E>, S: Storage<E>> Base<T, K, E, S> {
+    /// Runs `algorithm` on the residual network and reads the flow and cut back from it. Edges
+    /// of an undirected graph are in the storage both ways, each with the whole capacity
+    fn max_flow(
+        &self,
+        source: K,
+        sink: K,
+        algorithm: fn(&mut Network<E>, usize, usize) -> E,
+        undirected: bool,
+    ) -> Result<MaxFlow<'_, T, K, E>, MaxFlowError> {
+        let s = self.index_of(&source).ok_or(MaxFlowError::SourceNotFound)?;
+        let t = self.index_of(&sink).ok_or(MaxFlowError::SinkNotFound)?;
+        if s == t {
+            return Err(MaxFlowError::SourceIsSink);
+        }
+        let mut net = Network::new(&self.edges)?;
+        let value = algorithm(&mut net, s, t);
+        let nodes = &self.nodes;
+
+        // Reverse arcs hold exactly what went through their arc
+        let ends = |arc: usize| (net.heads[arc ^ 1], net.heads[arc]);
+        let sent = |arc: usize| net.residual[arc ^ 1];
+        // Undirected edges are an arc each way, and what goes one way cancels out what goes the
+        // other
+        let arcs: HashMap<(usize, usize), usize> = if undirected {
+            (0..net.heads.len()).step_by(2).map(|arc| (ends(arc), arc)).collect()
+        } else {
+            HashMap::new()
+        };
+        let mut flows = Vec::new();
+        for arc in (0..net.heads.len()).step_by(2) {
+            let (a, b) = ends(arc);
+            let mut amount = sent(arc);
+            if let Some(&back) = arcs.get(&(b, a)) {
+                if amount <= sent(back) { continue; }
+                amount = amount - sent(back);
+            }
+            if amount > E::default() {
+                flows.push((&nodes[a], &nodes[b], amount));
+            }
+        }
+        let levels = net.levels(s);
+        let source_side =
+            (0..nodes.len()).filter(|&x| levels[x].is_some()).map(|x| &nodes[x]).collect();
+
+        Ok(MaxFlow { value, flows, source_side })
+    }
+}
@@ -1,77 +1,112 @@
-use std::{hash::Hash, mem};
-use std::
-    fmt::Debug
-;
+use std::{
+    collections::HashSet,
+    fmt::{self, Debug},
+    hash::Hash,
+};
 
+use base::Base;
 use thiserror::Error;
-use utils::{coords_to_idx, has_duplicates, idx_to_coords};
-
-use crate::utils::dedup;
+use utils::has_duplicates;
 
+mod base;
 mod utils;
 mod properties;
 mod traversal;
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Graph<T> {
-    nodes: Vec<Node<T>>,
-    edges: AdjMatrix,
+mod draw;
+mod directed;
+mod storage;
+mod paths;
+mod spanning;
+mod euler;
+mod degrees;
+mod flow;
+mod matching;
+mod coloring;
+
+pub use coloring::ColoringError;
+pub use degrees::{is_graphical, FromDegreeSequenceError};
+pub use directed::{DiGraph, SparseDiGraph};
+pub use draw::{Color, DrawError, DrawOptions, Image, Layout};
+pub use flow::{EdgeFlow, MaxFlow, MaxFlowError};
+pub use matching::{min_cost_assignment, Assignment, AssignmentError};
+pub use paths::{
+    AStarError, AStarSearch, AllPairs, BellmanFordError, DijkstraError, FloydWarshallError, Path,
+    ShortestPaths, Weight,
+};
+pub use properties::{Bipartition, BlockCutNode, OddCycle};
+pub use storage::{AdjList, AdjMatrix, Storage};
+pub use traversal::{BFSError, BFSTree, DFSError, DFSWalk};
+pub use utils::{DisjointSet, KeyedDisjointSet};
+
+/// Anything that can be used as the name of a node. Implemented for every type with the right
+/// bounds, so there's no need to implement it by hand
+pub trait Key: Eq + Hash + Ord + Clone {}
+
+impl<K: Eq + Hash + Ord + Clone> Key for K {}
+
+/// Undirected graph. `K` is the type of the nodes' names, which must be unique within a graph,
+/// and `E` is the value carried by each edge (nothing, by default). `S` is where the edges are
+/// kept: see `AdjMatrix` and `AdjList`
+#[derive(Clone)]
+pub struct Graph<T, K = char, E = (), S = AdjMatrix<E>> {
+    base: Base<T, K, E, S>,
 }
 
-/// The adjacency matrix: must always be symmetrical*, binary and has zeros across the diagonal. true
-/// means the two nodes are adjacent, false means they're not
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct AdjMatrix {
-    values: Vec<bool>,
-    n: usize,
-}
-
-impl AdjMatrix {
-    pub fn from_mat<const N: usize>(m: [[bool; N]; N]) -> Result<Self, FromMatrixError> {
-        // Check for symmetry:
-        for y in 0..N {
-            for x in 0..N {
-                if m[x][y] != m[y][x] {
-                    return Err(FromMatrixError::IsNotSymmetric);
-                }
-            }
-            if m[y][y] {
-                return Err(FromMatrixError::NonEmptyDiagonal);
-            }
-        }
-        Ok(AdjMatrix {
-            values: m.into_iter().flatten().collect(),
-            n: N,
-        })
+impl<T, K, E, S> PartialEq for Graph<T, K, E, S>
+where
+    Node<T, K>: PartialEq,
+    S: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
     }
+}
 
-    /// Panics if either a or b are out of bounds
-    fn has_adjacency(&self, a: usize, b: usize) -> Option<bool> {
-        if a > self.n || b > self.n {
-            None
-        } else {
-            // Matrix is symmetric, a and b can be swapped
-            Some(self.values[coords_to_idx(a, b, self.n)])
-        }
-    }
+impl<T, K, E, S> Eq for Graph<T, K, E, S>
+where
+    Node<T, K>: Eq,
+    S: Eq,
+{
+}
 
-    /// Assumes x is in bounds
-    fn get_adjacent(&self, x: usize) -> &[bool] {
-        &self.values[x*self.n..(x+1)*self.n]
+impl<T, K, E, S> Debug for Graph<T, K, E, S>
+where
+    Node<T, K>: Debug,
+    S: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.base.debug("Graph", f)
     }
 }
 
+/// Same as `Graph`, but with sparse storage. Meant for big graphs with few edges per node: build
+/// one with the `_in` constructors or `SparseGraph::empty()`, or turn a graph into one with
+/// `into_storage`
+pub type SparseGraph<T, K = char, E = ()> = Graph<T, K, E, AdjList<E>>;
+
 /// Basic Node type, which the graph connects. Note that changing its name or its value (if any)
 /// will NOT change the structure of the graph.
 #[derive(Clone, Debug, PartialEq, Eq, Copy, Hash)]
-pub struct Node<T> {
-    pub name: char,
+pub struct Node<T, K = char> {
+    pub name: K,
     pub value: T,
 }
 
-impl<T> Node<T> {
+/// Each node along with the nodes it's adjacent to, as taken by the `from_list` constructors
+pub type AdjacencyLists<T, K = char> = Vec<(Node<T, K>, Vec<Node<T, K>>)>;
+
+/// Same as `AdjacencyLists`, with the value of the edge next to each neighbour
+pub type WeightedAdjacencyLists<T, K = char, E = ()> = Vec<(Node<T, K>, Vec<(Node<T, K>, E)>)>;
+
+/// An edge, as a pair of references to its endpoints
+pub type Edge<'a, T, K = char> = (&'a Node<T, K>, &'a Node<T, K>);
+
+/// An edge along with its value
+pub type WeightedEdge<'a, T, K = char, E = ()> = (&'a Node<T, K>, &'a Node<T, K>, &'a E);
+
+impl<T, K> Node<T, K> {
     /// Convenience, normal constructor may also be used without problems
-    pub fn new(name: char, value: T) -> Self {
+    pub fn new(name: K, value: T) -> Self {
         Self { name, value }
     }
 }
@@ -97,107 +132,166 @@ pub enum FromListError {
     MalformedLists,
 }
 
-impl<T> Graph<T> {
-    /// TODO: Change the bounds from Hash + Eq to something easier? Though these seem optimal
+impl<T, K: Key> Graph<T, K> {
+    /// Each node along with the nodes it's adjacent to. Every node needs its own list, and the
+    /// lists must agree with each other
+    pub fn from_list(lists: AdjacencyLists<T, K>) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+    {
+        Self::from_list_in(lists)
+    }
+
+    /// Returns None if the matrix is non-symmetrical or has at least in the main diagonal, or also
+    /// if one of the names is repeated
+    pub fn from_matrix<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[bool; N]; N],
+    ) -> Result<Self, FromMatrixError> {
+        Self::from_matrix_in(names, values, m)
+    }
+}
+
+impl<T, K: Key, S: Storage<()>> Graph<T, K, (), S> {
+    /// Same as `from_list`, but with the storage of the graph type: `SparseGraph::from_list_in`
+    /// never goes through a matrix
+    pub fn from_list_in(lists: AdjacencyLists<T, K>) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+    {
+        Self::from_weighted_list_in(
+            lists
+                .into_iter()
+                .map(|(main, adjs)| (main, adjs.into_iter().map(|n| (n, ())).collect()))
+                .collect(),
+        )
+    }
+
+    /// Same as `from_matrix`, but with the storage of the graph type
+    pub fn from_matrix_in<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[bool; N]; N],
+    ) -> Result<Self, FromMatrixError> {
+        Self::from_weighted_matrix_in(names, values, m.map(|row| row.map(|b| b.then_some(()))))
+    }
+
+    /// Makes a ~ b.
     ///
-    /// This function is probably pretty awful in complexity, space and efficiency and should likely be
-    /// rewritten. TODO as well
-    pub fn from_list(lists: Vec<(Node<T>, Vec<Node<T>>)>) -> Result<Self, FromListError>
+    /// Returns whether the edge is new (false means it was already there)
+    pub fn add_edge(&mut self, a: K, b: K) -> Result<bool, EdgeEditError> {
+        Ok(self.add_weighted_edge(a, b, ())?.is_none())
+    }
+
+    /// Adds the edge if it wasn't there, removes it otherwise.
+    ///
+    /// Returns whether a ~ b after the change
+    pub fn toggle_edge(&mut self, a: K, b: K) -> Result<bool, EdgeEditError> {
+        let (a_idx, b_idx) = self.base.edge_indices(a, b)?;
+        let now_there = self.base.edges.weight(a_idx, b_idx).is_none();
+        self.set_edge(a_idx, b_idx, now_there.then_some(()));
+        Ok(now_there)
+    }
+}
+
+impl<T, K: Key, E> Graph<T, K, E> {
+    /// Same as `from_matrix`, but each `Some` holds the value of its edge. Both halves of the
+    /// matrix must agree on the values too
+    pub fn from_weighted_matrix<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[Option<E>; N]; N],
+    ) -> Result<Self, FromMatrixError>
     where
-        Node<T>: Hash + Eq,
+        E: PartialEq,
     {
-        // Pick out nodes from the indexes and lists
-        // This determines the order in the matrix
-        let mut nodes: Vec<&Node<T>> = Vec::new();
-        for (main, adjs) in &lists {
-            nodes.push(main);
-            for n in adjs {
-                nodes.push(n);
-            }
-        }
+        Self::from_weighted_matrix_in(names, values, m)
+    }
 
-        dedup(&mut nodes);
+    /// Same as `from_list`, but each neighbour comes with the value of the edge. Both ends of an
+    /// edge must agree on its value
+    pub fn from_weighted_list(lists: WeightedAdjacencyLists<T, K, E>) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+        E: PartialEq,
+    {
+        Self::from_weighted_list_in(lists)
+    }
+}
 
-        if has_duplicates(nodes.iter().map(|n| n.name)) {
-            return Err(FromListError::NodesArentUnique);
-        }
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// A graph without nodes
+    pub fn empty() -> Self {
+        Graph { base: Base::new(Vec::new(), S::empty(0)) }
+    }
 
-        let ordre = nodes.len();
-        let mut matriu = vec![None; ordre * ordre];
-        for i in 0..ordre {
-            matriu[coords_to_idx(i, i, ordre)] = Some(false);
+    /// Same as `from_weighted_matrix`, but with the storage of the graph type
+    pub fn from_weighted_matrix_in<const N: usize>(
+        names: [K; N],
+        values: [T; N],
+        m: [[Option<E>; N]; N],
+    ) -> Result<Self, FromMatrixError>
+    where
+        E: PartialEq,
+    {
+        if has_duplicates(&names) {
+            return Err(FromMatrixError::NodesArentUnique)?;
         }
-
-        for (i, u) in nodes.iter().enumerate() {
-            for (j, v) in nodes.iter().enumerate() {
-                let index = coords_to_idx(i, j, ordre);
-                let index_symmetric = coords_to_idx(i, j, ordre);
-
-                let adj_list = &lists
-                    .iter()
-                    .find(|(x, _)| &x == u)
-                    .ok_or(FromListError::MalformedLists)?
-                    .1;
-                if adj_list.contains(v) {
-                    // u ~ v ?
-                    // If we already said there hadn't, the list is not well formed
-                    if matriu[index] == Some(false) || matriu[index_symmetric] == Some(false) {
-                        return Err(FromListError::MalformedLists);
-                    }
-                    matriu[index] = Some(true);
-                    matriu[index_symmetric] = Some(true);
-                } else {
-                    // If we already said there had, the list is not well formed
-                    if matriu[index] == Some(true) || matriu[index_symmetric] == Some(true) {
-                        return Err(FromListError::MalformedLists);
-                    }
-                    matriu[index] = Some(false);
-                    matriu[index_symmetric] = Some(false);
+        // Check for symmetry:
+        for (y, row) in m.iter().enumerate() {
+            for (x, v) in row.iter().enumerate() {
+                if *v != m[x][y] {
+                    return Err(FromMatrixError::IsNotSymmetric);
                 }
             }
         }
+        let nodes = names.into_iter().zip(values).map(|(c, v)| Node::new(c, v)).collect();
+        let edges = storage::from_matrix(m)?;
 
-        //  `lists` still owns the nodes, so we rebuild it to take ownership
-        let mut nodes: Vec<Node<T>> = Vec::new();
-        // And from the lists
-        for (main, adjs) in lists {
-            nodes.push(main);
-            for n in adjs {
-                nodes.push(n);
-            }
-        }
-        dedup(&mut nodes);
+        Ok(Graph { base: Base::new(nodes, edges) })
+    }
+
+    /// Same as `from_weighted_list`, but with the storage of the graph type
+    pub fn from_weighted_list_in(
+        lists: WeightedAdjacencyLists<T, K, E>,
+    ) -> Result<Self, FromListError>
+    where
+        Node<T, K>: Hash + Eq,
+        E: PartialEq,
+    {
+        let mains: HashSet<K> = lists.iter().map(|(main, _)| main.name.clone()).collect();
+        let DiGraph { base } = DiGraph::from_weighted_list_in(lists)?;
 
-        if matriu.iter().any(Option::is_none) {
+        if base.nodes.iter().any(|n| !mains.contains(&n.name)) {
+            return Err(FromListError::MalformedLists);
+        }
+        // Both lists must agree
+        if !storage::is_symmetric(&base.edges) {
             return Err(FromListError::MalformedLists);
         }
 
-        let values: Vec<bool> = matriu
-            .into_iter()
-            .map(|b| b.expect("We've already verified they're all Some"))
-            .collect();
+        Ok(Graph { base })
+    }
 
-        // Check symmetry and zeros down the diagonal, just in case
-        for i in 0..ordre * ordre {
-            let (x, y) = idx_to_coords(i, ordre);
-            let i_prime = coords_to_idx(y, x, ordre);
+    /// Same graph, with its edges kept in another kind of storage
+    pub fn into_storage<S2: Storage<E>>(self) -> Graph<T, K, E, S2> {
+        Graph { base: self.base.into_storage() }
+    }
 
-            if values[i] != values[i_prime] {
-                return Err(FromListError::MalformedLists);
-            }
-            if x == y && values[i] {
-                return Err(FromListError::MalformedLists);
-            }
-        }
+    /// Position of the node in `nodes`, which is also its position in the storage
+    fn index_of(&self, x: &K) -> Option<usize> {
+        self.base.index_of(x)
+    }
 
-        Ok(Graph {
-            nodes: nodes.into_iter().collect(),
-            edges: AdjMatrix { values, n: ordre },
-        })
+    /// Every node, in the order the graph keeps them. Results given as matrices, like
+    /// `floyd_warshall`'s, use this same order
+    pub fn nodes(&self) -> &[Node<T, K>] {
+        &self.base.nodes
     }
 
-    pub fn get_node(&self, x: char) -> Option<&Node<T>> {
-        self.nodes.iter().find(|n| n.name == x)
+    pub fn get_node(&self, x: K) -> Option<&Node<T, K>> {
+        self.base.get_node(x)
     }
 
     /// Change the name of a node. 
@@ -206,16 +300,8 @@ impl<T> Graph<T> {
     ///
     /// Errors if the name is already taken or if the wanted node does
     /// not exist
-    pub fn change_node_name(&mut self, original: char, new: char) -> Result<&T, NodeNameChangeError> {
-        if self.nodes.iter().any(|x| x.name == new) {
-            return Err(NodeNameChangeError::NameAlreadyTaken);
-        }
-        if let Some(node) = self.nodes.iter_mut().find(|n| n.name == original) {
-            node.name = new;
-            Ok(&node.value)
-        } else {
-            Err(NodeNameChangeError::NodeNotFound)
-        }
+    pub fn change_node_name(&mut self, original: K, new: K) -> Result<&T, NodeNameChangeError> {
+        self.base.change_node_name(original, new)
     }
 
     /// Change the value of a node.
@@ -223,46 +309,100 @@ impl<T> Graph<T> {
     /// Returns the value of the changed node
     ///
     /// Errors only if the wanted node does not exist
-    pub fn change_node_value(&mut self, node: char, mut new_val: T) -> Result<T, ()> {
-        if let Some(node) = self.nodes.iter_mut().find(|n| n.name == node) {
-            mem::swap(&mut new_val, &mut node.value);
-            Ok(new_val)
-        } else {
-            Err(())
-        }
+    pub fn change_node_value(&mut self, node: K, new_val: T) -> Result<T, NodeValueChangeError> {
+        self.base.change_node_value(node, new_val)
     }
 
-    /// Returns None if the matrix is non-symmetrical or has at least in the main diagonal, or also
-    /// if one of the names is repeated
-    pub fn from_matrix<const N: usize>(
-        names: [char; N],
-        values: [T; N],
-        m: [[bool; N]; N],
-    ) -> Result<Self, FromMatrixError> {
-        if has_duplicates(names) {
-            return Err(FromMatrixError::NodesArentUnique)?;
-        }
-        let nodes = names.into_iter().zip(values).map(|(c, v)| Node::new(c, v)).collect();
-        let edges = AdjMatrix::from_mat(m)?;
+    /// Returns None if either or both of the nodes do not exist in the graph
+    pub fn has_adjacency(&self, a: K, b: K) -> Option<bool> {
+        self.base.has_adjacency(a, b)
+    }
+    pub fn adjacent_nodes(&self, c: K) -> Option<Vec<&Node<T, K>>> {
+        self.base.neighbours(c)
+    }
 
-        Ok(Graph { nodes, edges })
+    /// Amount of nodes adjacent to c. Returns None if it does not exist in the graph
+    pub fn degree(&self, c: K) -> Option<usize> {
+        let i = self.index_of(&c)?;
+        Some(self.base.edges.neighbours(i).count())
     }
 
-    /// Returns None if either or both of the nodes do not exist in the graph
-    pub fn has_adjacency(&self, a: char, b: char) -> Option<bool> {
-        let a_idx = self.nodes.iter().enumerate().find(|(_, x)| x.name == a)?.0;
-        let b_idx = self.nodes.iter().enumerate().find(|(_, x)| x.name == b)?.0;
-        self.edges.has_adjacency(a_idx, b_idx)
+    /// Like `adjacent_nodes`, along with the value of the edge that leads to each neighbour
+    pub fn adjacent_edges(&self, c: K) -> Option<Vec<(&Node<T, K>, &E)>> {
+        let i = self.index_of(&c)?;
+        Some(self.base.edges.neighbours(i).map(|(j, w)| (&self.base.nodes[j], w)).collect())
+    }
+
+    /// Returns None if either or both of the nodes do not exist in the graph, or if they're not
+    /// adjacent
+    pub fn edge_weight(&self, a: K, b: K) -> Option<&E> {
+        self.base.edge_weight(a, b)
+    }
+
+    /// Adds an isolated node
+    ///
+    /// Errors if its name is already taken
+    pub fn add_node(&mut self, node: Node<T, K>) -> Result<(), AddNodeError> {
+        self.base.add_node(node)
     }
-    pub fn adjacent_nodes(&self, c: char) -> Option<Vec<&Node<T>>> {
-        let i = self.nodes.iter().position(|x| x.name == c)?;
-        let is_part_of = self.edges.get_adjacent(i);
-        if is_part_of.len() != self.nodes.len() { return None; }
 
-        Some(self.nodes.iter().zip(is_part_of).filter(|(_n, b)| **b ).map(|(n, _)| n).collect())
+    /// Removes a node along with all of its edges.
+    ///
+    /// Returns the removed node
+    pub fn remove_node(&mut self, c: K) -> Result<Node<T, K>, RemoveNodeError> {
+        self.base.remove_node(c)
+    }
+
+    /// Makes a ~ b, with `weight` as the value of the edge.
+    ///
+    /// Returns the previous value if the edge was already there
+    pub fn add_weighted_edge(&mut self, a: K, b: K, weight: E) -> Result<Option<E>, EdgeEditError>
+    where
+        E: Clone,
+    {
+        let (a_idx, b_idx) = self.base.edge_indices(a, b)?;
+        Ok(self.set_edge(a_idx, b_idx, Some(weight)))
+    }
+
+    /// Makes a !~ b.
+    ///
+    /// Returns whether there was an edge to remove
+    pub fn remove_edge(&mut self, a: K, b: K) -> Result<bool, EdgeEditError> {
+        let (a_idx, b_idx) = self.base.edge_indices(a, b)?;
+        self.base.edges.set(b_idx, a_idx, None);
+        Ok(self.base.edges.set(a_idx, b_idx, None).is_some())
+    }
+
+    /// Sets both a -> b and b -> a, keeping the storage symmetric. Returns the previous value
+    fn set_edge(&mut self, a: usize, b: usize, value: Option<E>) -> Option<E>
+    where
+        E: Clone,
+    {
+        self.base.edges.set(b, a, value.clone());
+        self.base.edges.set(a, b, value)
     }
 }
 
+#[derive(Debug, Error)]
+pub enum AddNodeError {
+    #[error("name was already taken")]
+    NameAlreadyTaken,
+}
+
+#[derive(Debug, Error)]
+pub enum RemoveNodeError {
+    #[error("no such node in the graph")]
+    NodeNotFound,
+}
+
+#[derive(Debug, Error)]
+pub enum EdgeEditError {
+    #[error("no such node in the graph")]
+    NodeNotFound,
+    #[error("nodes may not be adjacent to themselves")]
+    SelfReferentialNode,
+}
+
 #[derive(Debug, Error)]
 pub enum NodeNameChangeError {
     #[error("name was already taken")]
@@ -270,3 +410,9 @@ pub enum NodeNameChangeError {
     #[error("no such node in the graph")]
     NodeNotFound
 }
+
+#[derive(Debug, Error)]
+pub enum NodeValueChangeError {
+    #[error("no such node in the graph")]
+    NodeNotFound,
+}
@@ -0,0 +1,422 @@
+// Matchings: sets of edges that don't share any node
+
+use std::{collections::VecDeque, ops::Sub};
+
+use thiserror::Error;
+
+use crate::{utils::coords_to_idx, Edge, Graph, Key, Node, OddCycle, Storage, Weight};
+
+#[derive(Debug, Error)]
+pub enum AssignmentError {
+    #[error("the cost matrix does not have rows × columns entries")]
+    WrongDimensions,
+    #[error("the graph is not bipartite")]
+    NotBipartite,
+    #[error("some node on one side is not adjacent to some node on the other")]
+    NotComplete,
+}
+
+/// Pairs of a matching, along with its total cost
+pub type Assignment<'a, T, K = char, E = ()> = (Vec<Edge<'a, T, K>>, E);
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// A largest possible set of edges without a node in common, found with Hopcroft–Karp. Each
+    /// pair goes from the left side of `bipartition` to the right one, in the order of the graph.
+    /// Like `bipartition`, errors with an odd cycle if the graph isn't bipartite
+    pub fn maximum_bipartite_matching(&self) -> Result<Vec<Edge<'_, T, K>>, OddCycle<'_, T, K>> {
+        let left = self.left_side()?;
+        let mates = self.hopcroft_karp(&left);
+        Ok(left
+            .into_iter()
+            .filter_map(|u| Some((&self.base.nodes[u], &self.base.nodes[mates[u]?])))
+            .collect())
+    }
+
+    /// A smallest possible set of nodes touching every edge, in the order of the graph. In a
+    /// bipartite graph it's as big as a maximum matching (König's theorem) and can be built out of
+    /// one. Errors with an odd cycle if the graph isn't bipartite
+    pub fn bipartite_vertex_cover(&self) -> Result<Vec<&Node<T, K>>, OddCycle<'_, T, K>> {
+        let left = self.left_side()?;
+        let mates = self.hopcroft_karp(&left);
+        let mut is_left = vec![false; self.base.nodes.len()];
+        for &u in &left {
+            is_left[u] = true;
+        }
+
+        // Everything reachable from an unmatched left node by alternating paths: any edge from
+        // left to right, only matched ones from right to left
+        let mut reached = vec![false; self.base.nodes.len()];
+        let mut queue: VecDeque<usize> =
+            left.iter().copied().filter(|&u| mates[u].is_none()).collect();
+        for &u in &queue {
+            reached[u] = true;
+        }
+        while let Some(u) = queue.pop_front() {
+            for (v, _) in self.base.edges.neighbours(u) {
+                if reached[v] { continue; }
+                reached[v] = true;
+                if let Some(w) = mates[v] {
+                    if !reached[w] {
+                        reached[w] = true;
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+
+        // Left nodes that weren't reached, right nodes that were
+        Ok((0..self.base.nodes.len())
+            .filter(|&x| is_left[x] != reached[x])
+            .map(|x| &self.base.nodes[x])
+            .collect())
+    }
+
+    /// A largest possible set of edges without a node in common, for any graph, found with
+    /// Edmonds' blossom algorithm. Each pair starts with the node that comes first in the graph
+    pub fn maximum_matching(&self) -> Vec<Edge<'_, T, K>> {
+        let mates = Blossoms::new(self).maximum_matching();
+        (0..self.base.nodes.len())
+            .filter_map(|x| {
+                mates[x].filter(|&y| x < y).map(|y| (&self.base.nodes[x], &self.base.nodes[y]))
+            })
+            .collect()
+    }
+
+    /// Whether the edges are all in the graph and every node is in exactly one of them
+    pub fn is_perfect_matching(&self, matching: &[Edge<'_, T, K>]) -> bool {
+        let mut covered = vec![false; self.base.nodes.len()];
+        for (a, b) in matching {
+            let (Some(x), Some(y)) = (self.index_of(&a.name), self.index_of(&b.name)) else {
+                return false;
+            };
+            if self.base.edges.weight(x, y).is_none() || covered[x] || covered[y] {
+                return false;
+            }
+            covered[x] = true;
+            covered[y] = true;
+        }
+        covered.into_iter().all(|c| c)
+    }
+
+    /// Whether some matching covers every node
+    pub fn has_perfect_matching(&self) -> bool {
+        2 * self.maximum_matching().len() == self.base.nodes.len()
+    }
+
+    /// Cheapest way of pairing every node on the smaller side of a complete bipartite graph with a
+    /// different node on the other side, the value of the edges being their cost. Pairs start on
+    /// the smaller side (the left one if they're the same size) and follow its order in the graph
+    pub fn min_cost_assignment(&self) -> Result<Assignment<'_, T, K, E>, AssignmentError>
+    where
+        E: Weight + Sub<Output = E>,
+    {
+        let left = self.left_side().map_err(|_| AssignmentError::NotBipartite)?;
+        let mut is_left = vec![false; self.base.nodes.len()];
+        for &u in &left {
+            is_left[u] = true;
+        }
+        let right: Vec<usize> = (0..self.base.nodes.len()).filter(|&x| !is_left[x]).collect();
+        let (rows, cols) = if left.len() <= right.len() { (left, right) } else { (right, left) };
+
+        let mut costs = Vec::with_capacity(rows.len() * cols.len());
+        for &r in &rows {
+            for &c in &cols {
+                costs.push(*self.base.edges.weight(r, c).ok_or(AssignmentError::NotComplete)?);
+            }
+        }
+
+        let (pairs, total) = min_cost_assignment(&costs, rows.len(), cols.len())?;
+        let nodes = &self.base.nodes;
+        let pairs = pairs.into_iter().map(|(r, c)| (&nodes[rows[r]], &nodes[cols[c]])).collect();
+        Ok((pairs, total))
+    }
+
+    /// Positions of the left side of `bipartition`
+    fn left_side(&self) -> Result<Vec<usize>, OddCycle<'_, T, K>> {
+        let parts = self.bipartition()?;
+        let mut left: Vec<usize> =
+            parts.left.iter().filter_map(|n| self.index_of(&n.name)).collect();
+        left.sort_unstable();
+        Ok(left)
+    }
+
+    /// Mate of every node, by position, in a maximum matching. `left` must be one side of a
+    /// bipartition. Each phase finds the shortest augmenting paths with a BFS, then flips as many
+    /// of them as it can without them sharing nodes
+    fn hopcroft_karp(&self, left: &[usize]) -> Vec<Option<usize>> {
+        let n = self.base.nodes.len();
+        let lists: Vec<Vec<usize>> =
+            (0..n).map(|x| self.base.edges.neighbours(x).map(|(y, _)| y).collect()).collect();
+        let mut mates: Vec<Option<usize>> = vec![None; n];
+        // Layer of each left node in the current phase, None if it can't be used anymore
+        let mut layers: Vec<Option<usize>> = vec![None; n];
+
+        loop {
+            let mut queue = VecDeque::new();
+            for &u in left {
+                layers[u] = if mates[u].is_none() { Some(0) } else { None };
+                if mates[u].is_none() {
+                    queue.push_back(u);
+                }
+            }
+            // Layer of the first left node next to a free right node: the shortest augmenting
+            // paths end there, so nothing past it is needed
+            let mut shortest: Option<usize> = None;
+            while let Some(u) = queue.pop_front() {
+                if shortest.is_some_and(|s| layers[u] > Some(s)) { break; }
+                for &v in &lists[u] {
+                    match mates[v] {
+                        None => { shortest.get_or_insert(layers[u].unwrap()); }
+                        Some(w) if layers[w].is_none() && shortest.is_none() => {
+                            layers[w] = layers[u].map(|l| l + 1);
+                            queue.push_back(w);
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+            let Some(shortest) = shortest else { return mates };
+
+            let mut next = vec![0; n];
+            for &root in left {
+                if mates[root].is_some() { continue; }
+
+                // Left nodes on the current path, and the right node taken out of each of them
+                let mut stack = vec![root];
+                let mut via: Vec<usize> = Vec::new();
+                while let Some(&u) = stack.last() {
+                    let Some(&v) = lists[u].get(next[u]) else {
+                        layers[u] = None;
+                        stack.pop();
+                        via.pop();
+                        continue;
+                    };
+                    next[u] += 1;
+
+                    match mates[v] {
+                        None if layers[u] == Some(shortest) => {
+                            via.push(v);
+                            for (&u, &v) in stack.iter().zip(&via) {
+                                mates[u] = Some(v);
+                                mates[v] = Some(u);
+                            }
+                            break;
+                        }
+                        Some(w)
+                            if layers[u] < Some(shortest)
+                                && layers[w].is_some()
+                                && layers[w] == layers[u].map(|l| l + 1) =>
+                        {
+                            via.push(v);
+                            stack.push(w);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Cheapest way of giving each row of a cost matrix a different column, or each column a different
+/// row if there are more rows than columns, with the Hungarian algorithm. The matrix is row-major,
+/// like `AdjMatrix`. Returns the (row, column) pairs, sorted, and their total cost
+pub fn min_cost_assignment<E: Weight + Sub<Output = E>>(
+    costs: &[E],
+    rows: usize,
+    cols: usize,
+) -> Result<(Vec<(usize, usize)>, E), AssignmentError> {
+    if costs.len() != rows * cols {
+        return Err(AssignmentError::WrongDimensions);
+    }
+    if rows > cols {
+        let transposed: Vec<E> = (0..cols)
+            .flat_map(|c| (0..rows).map(move |r| costs[coords_to_idx(c, r, cols)]))
+            .collect();
+        let (pairs, total) = min_cost_assignment(&transposed, cols, rows)?;
+        let mut pairs: Vec<(usize, usize)> = pairs.into_iter().map(|(c, r)| (r, c)).collect();
+        pairs.sort_unstable();
+        return Ok((pairs, total));
+    }
+
+    // Potentials for rows and columns, keeping cost + column - row >= 0 with equality on the
+    // matched pairs. Both only ever go up, so unsigned costs never go below zero along the way.
+    // They're 1-based: column 0 is a placeholder for the row being added
+    let cost = |r: usize, c: usize| costs[coords_to_idx(c - 1, r - 1, cols)];
+    let mut row_pot = vec![E::default(); rows + 1];
+    let mut col_pot = vec![E::default(); cols + 1];
+    // Row matched to each column, 0 for none
+    let mut owner = vec![0; cols + 1];
+    // Previous column on the alternating path to each column
+    let mut way = vec![0; cols + 1];
+
+    for r in 1..=rows {
+        owner[0] = r;
+        let mut c0 = 0;
+        let mut slack: Vec<Option<E>> = vec![None; cols + 1];
+        let mut used = vec![false; cols + 1];
+
+        // Grows a tree of tight edges, lowering potentials when stuck, until a free column shows up
+        loop {
+            used[c0] = true;
+            let r0 = owner[c0];
+            let mut best: Option<(E, usize)> = None;
+            for c in 1..=cols {
+                if used[c] { continue; }
+                let reduced = cost(r0, c) + col_pot[c] - row_pot[r0];
+                if slack[c].is_none_or(|s| reduced < s) {
+                    slack[c] = Some(reduced);
+                    way[c] = c0;
+                }
+                let s = slack[c].unwrap();
+                if best.is_none_or(|(b, _)| s < b) {
+                    best = Some((s, c));
+                }
+            }
+
+            let (delta, c1) = best.expect("there are at least as many columns as rows");
+            for c in 0..=cols {
+                if used[c] {
+                    row_pot[owner[c]] = row_pot[owner[c]] + delta;
+                    col_pot[c] = col_pot[c] + delta;
+                } else if let Some(s) = slack[c] {
+                    slack[c] = Some(s - delta);
+                }
+            }
+            c0 = c1;
+            if owner[c0] == 0 { break; }
+        }
+
+        // Flip the alternating path back to the placeholder column
+        while c0 != 0 {
+            let c1 = way[c0];
+            owner[c0] = owner[c1];
+            c0 = c1;
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> =
+        (1..=cols).filter(|&c| owner[c] != 0).map(|c| (owner[c] - 1, c - 1)).collect();
+    pairs.sort_unstable();
+    let total = pairs.iter().fold(E::default(), |t, &(r, c)| t + cost(r + 1, c + 1));
+    Ok((pairs, total))
+}
+
+/// State of Edmonds' algorithm, by position. Each search grows an alternating tree from an
+/// unmatched root; odd cycles found along the way (blossoms) are shrunk into their base, which
+/// then behaves as a single node until the search is over
+struct Blossoms {
+    lists: Vec<Vec<usize>>,
+    mates: Vec<Option<usize>>,
+    /// Previous node on the alternating path, for the odd nodes of the tree
+    parents: Vec<Option<usize>>,
+    /// Base of the blossom each node has been shrunk into, itself if none
+    bases: Vec<usize>,
+    /// Even nodes of the tree, the ones the search goes on from
+    used: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+impl Blossoms {
+    fn new<T, K: Key, E, S: Storage<E>>(g: &Graph<T, K, E, S>) -> Self {
+        let n = g.base.nodes.len();
+        Blossoms {
+            lists: (0..n).map(|x| g.base.edges.neighbours(x).map(|(y, _)| y).collect()).collect(),
+            mates: vec![None; n],
+            parents: vec![None; n],
+            bases: (0..n).collect(),
+            used: vec![false; n],
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn maximum_matching(mut self) -> Vec<Option<usize>> {
+        for root in 0..self.lists.len() {
+            if self.mates[root].is_some() { continue; }
+
+            // Flip the path from the root to the unmatched node that was found
+            let mut x = self.augmenting_path(root);
+            while let Some(v) = x {
+                let p = self.parents[v].unwrap();
+                x = self.mates[p];
+                self.mates[v] = Some(p);
+                self.mates[p] = Some(v);
+            }
+        }
+        self.mates
+    }
+
+    /// The unmatched node an augmenting path from `root` ends at, if any. The path itself is left
+    /// in `parents`
+    fn augmenting_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.lists.len();
+        self.used = vec![false; n];
+        self.parents = vec![None; n];
+        self.bases = (0..n).collect();
+        self.used[root] = true;
+        self.queue = VecDeque::from([root]);
+
+        while let Some(v) = self.queue.pop_front() {
+            for i in 0..self.lists[v].len() {
+                let to = self.lists[v][i];
+                if self.bases[v] == self.bases[to] || self.mates[v] == Some(to) { continue; }
+
+                let to_is_even =
+                    to == root || self.mates[to].is_some_and(|m| self.parents[m].is_some());
+                if to_is_even {
+                    // Two even nodes are adjacent: that closes an odd cycle
+                    let base = self.common_base(v, to);
+                    let mut blossom = vec![false; n];
+                    self.mark_path(v, base, to, &mut blossom);
+                    self.mark_path(to, base, v, &mut blossom);
+                    for x in 0..n {
+                        if blossom[self.bases[x]] {
+                            self.bases[x] = base;
+                            if !self.used[x] {
+                                self.used[x] = true;
+                                self.queue.push_back(x);
+                            }
+                        }
+                    }
+                } else if self.parents[to].is_none() {
+                    self.parents[to] = Some(v);
+                    let Some(m) = self.mates[to] else { return Some(to) };
+                    self.used[m] = true;
+                    self.queue.push_back(m);
+                }
+            }
+        }
+        None
+    }
+
+    /// Lowest common ancestor of the blossoms of a and b, walking up the tree
+    fn common_base(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = vec![false; self.lists.len()];
+        loop {
+            a = self.bases[a];
+            seen[a] = true;
+            let Some(m) = self.mates[a] else { break };
+            a = self.parents[m].unwrap();
+        }
+        loop {
+            b = self.bases[b];
+            if seen[b] {
+                return b;
+            }
+            b = self.parents[self.mates[b].unwrap()].unwrap();
+        }
+    }
+
+    /// Marks the blossoms between v and the base as part of the new one, and points the parents
+    /// along the way the other way around the cycle, through `child`
+    fn mark_path(&mut self, mut v: usize, base: usize, mut child: usize, blossom: &mut [bool]) {
+        while self.bases[v] != base {
+            let m = self.mates[v].unwrap();
+            blossom[self.bases[v]] = true;
+            blossom[self.bases[m]] = true;
+            self.parents[v] = Some(child);
+            child = m;
+            v = self.parents[m].unwrap();
+        }
+    }
+}
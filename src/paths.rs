@@ -0,0 +1,483 @@
+// Shortest paths over weighted graphs
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    ops::Add,
+};
+
+use thiserror::Error;
+
+use crate::{base::Base, utils::coords_to_idx, DiGraph, Graph, Key, Node, Storage};
+
+/// Edge values that can be used as lengths: they're added up along paths and compared. Their
+/// `Default` is taken as zero, which is the case for every number type
+pub trait Weight: Copy + PartialOrd + Add<Output = Self> + Default {}
+
+impl<W: Copy + PartialOrd + Add<Output = W> + Default> Weight for W {}
+
+#[derive(Debug, Error)]
+pub enum DijkstraError {
+    #[error("source vertex was not found in the graph")]
+    SourceNotFound,
+    #[error("target vertex was not found in the graph")]
+    TargetNotFound,
+    #[error("found an edge with negative weight")]
+    NegativeWeight,
+}
+
+#[derive(Debug, Error)]
+pub enum BellmanFordError<'a, T, K = char> {
+    #[error("source vertex was not found in the graph")]
+    SourceNotFound,
+    /// The nodes of the cycle in order, each one having an edge to the next and the last one to
+    /// the first
+    #[error("found a negative cycle reachable from the source")]
+    NegativeCycle(Vec<&'a Node<T, K>>),
+}
+
+#[derive(Debug, Error)]
+pub enum FloydWarshallError {
+    #[error("found a negative cycle")]
+    NegativeCycle,
+}
+
+#[derive(Debug, Error)]
+pub enum AStarError {
+    #[error("starting vertex was not found in the graph")]
+    StartNotFound,
+    #[error("goal vertex was not found in the graph")]
+    GoalNotFound,
+    #[error("found an edge with negative weight")]
+    NegativeWeight,
+}
+
+/// What an A* search came up with
+#[derive(Debug, Clone)]
+pub struct AStarSearch<'a, T, K = char, E = ()> {
+    /// Cheapest path from start to goal, None if the goal can't be reached
+    pub path: Option<Path<'a, T, K, E>>,
+    /// How many times a node was taken out of the queue to look at its neighbours
+    pub expanded: usize,
+}
+
+/// Shortest distances between every pair of nodes. Nodes are referred to by their position in
+/// `nodes()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllPairs<E> {
+    n: usize,
+    /// Row-major n×n matrix: row a and column b is the distance from a to b, None if b can't be
+    /// reached
+    pub distances: Vec<Option<E>>,
+    /// Same layout, first step on the way from a to b
+    next: Vec<Option<usize>>,
+}
+
+impl<E> AllPairs<E> {
+    pub fn distance(&self, a: usize, b: usize) -> Option<&E> {
+        self.distances[coords_to_idx(b, a, self.n)].as_ref()
+    }
+
+    /// Positions of the nodes on a shortest path from a to b, both included. None if b can't be
+    /// reached
+    pub fn path(&self, a: usize, b: usize) -> Option<Vec<usize>> {
+        let mut path = vec![a];
+        let mut x = a;
+        while x != b {
+            x = self.next[coords_to_idx(b, x, self.n)]?;
+            path.push(x);
+        }
+        Some(path)
+    }
+}
+
+/// Shortest distances from a single node. Both maps are keyed by node name and only contain the
+/// nodes that can be reached
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<'a, T, K = char, E = ()> {
+    /// Length of the shortest path from the source
+    pub distances: HashMap<K, E>,
+    /// Previous node on that path, None for the source
+    pub predecessors: HashMap<K, Option<&'a Node<T, K>>>,
+}
+
+/// The nodes along a path, both ends included, and its total cost
+pub type Path<'a, T, K = char, E = ()> = (Vec<&'a Node<T, K>>, E);
+
+/// Distance to each node and the previous node on the way there, by position
+type Search<E> = (Vec<Option<E>>, Vec<Option<usize>>);
+
+impl<T, K: Key, E: Weight, S: Storage<E>> Graph<T, K, E, S> {
+    /// Shortest distances from `source` to every node it can reach. Weights can't be negative
+    pub fn dijkstra(&self, source: K) -> Result<ShortestPaths<'_, T, K, E>, DijkstraError> {
+        self.base.dijkstra(source)
+    }
+
+    /// Cheapest path from a to b. None if b can't be reached
+    pub fn shortest_path(&self, a: K, b: K) -> Result<Option<Path<'_, T, K, E>>, DijkstraError> {
+        self.base.shortest_path(a, b)
+    }
+
+    /// Shortest distances from `source`, allowing negative weights. Every edge can be walked both
+    /// ways, so a negative edge that can be reached is already a negative cycle: going back and
+    /// forth along it. The cycle is reported as its two ends
+    pub fn bellman_ford(
+        &self,
+        source: K,
+    ) -> Result<ShortestPaths<'_, T, K, E>, BellmanFordError<'_, T, K>> {
+        self.base.bellman_ford(source)
+    }
+
+    /// Cheapest path from start to goal, looking first at the nodes that `heuristic` says are
+    /// closer to the goal. The path is only sure to be the cheapest if the heuristic never
+    /// overestimates the remaining cost. Weights can't be negative
+    pub fn astar(
+        &self,
+        start: K,
+        goal: K,
+        heuristic: impl Fn(&Node<T, K>) -> E,
+    ) -> Result<AStarSearch<'_, T, K, E>, AStarError> {
+        self.base.astar(start, goal, heuristic)
+    }
+
+    /// Shortest distances between every pair of nodes. Since edges can be walked both ways, a
+    /// single negative edge makes for a negative cycle
+    pub fn floyd_warshall(&self) -> Result<AllPairs<E>, FloydWarshallError> {
+        floyd_warshall(&self.base.edges)
+    }
+}
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// Amount of edges on the shortest path between every pair of nodes, as a row-major n×n
+    /// matrix in the order of `nodes()`. None if there's no path
+    pub fn distance_matrix(&self) -> Vec<Option<usize>> {
+        distance_matrix(&self.base.edges)
+    }
+}
+
+impl<T, K: Key, E: Weight, S: Storage<E>> DiGraph<T, K, E, S> {
+    /// Shortest distances from `source` to every node it can reach, following edges forwards.
+    /// Weights can't be negative
+    pub fn dijkstra(&self, source: K) -> Result<ShortestPaths<'_, T, K, E>, DijkstraError> {
+        self.base.dijkstra(source)
+    }
+
+    /// Cheapest path from a to b. None if b can't be reached
+    pub fn shortest_path(&self, a: K, b: K) -> Result<Option<Path<'_, T, K, E>>, DijkstraError> {
+        self.base.shortest_path(a, b)
+    }
+
+    /// Shortest distances from `source`, following edges forwards. Weights can be negative, as long
+    /// as there's no negative cycle that can be reached from the source
+    pub fn bellman_ford(
+        &self,
+        source: K,
+    ) -> Result<ShortestPaths<'_, T, K, E>, BellmanFordError<'_, T, K>> {
+        self.base.bellman_ford(source)
+    }
+
+    /// Cheapest path from start to goal, looking first at the nodes that `heuristic` says are
+    /// closer to the goal, following edges forwards. The path is only sure to be the cheapest if the heuristic never
+    /// overestimates the remaining cost. Weights can't be negative
+    pub fn astar(
+        &self,
+        start: K,
+        goal: K,
+        heuristic: impl Fn(&Node<T, K>) -> E,
+    ) -> Result<AStarSearch<'_, T, K, E>, AStarError> {
+        self.base.astar(start, goal, heuristic)
+    }
+
+    /// Shortest distances between every pair of nodes, following edges forwards
+    pub fn floyd_warshall(&self) -> Result<AllPairs<E>, FloydWarshallError> {
+        floyd_warshall(&self.base.edges)
+    }
+}
+
+impl<T, K: Key, E, S: Storage<E>> DiGraph<T, K, E, S> {
+    /// Amount of edges on the shortest path from every node to every other one, as a row-major
+    /// n×n matrix in the order of `nodes()`. None if there's no path
+    pub fn distance_matrix(&self) -> Vec<Option<usize>> {
+        distance_matrix(&self.base.edges)
+    }
+}
+
+// What the searches from a single node look like on either kind of graph, by name
+impl<T, K: Key, E: Weight, S: Storage<E>> Base<T, K, E, S> {
+    fn dijkstra(&self, source: K) -> Result<ShortestPaths<'_, T, K, E>, DijkstraError> {
+        let s = self.index_of(&source).ok_or(DijkstraError::SourceNotFound)?;
+        let (distances, parents) = dijkstra(&self.edges, s)?;
+        Ok(shortest_paths(&self.nodes, distances, parents))
+    }
+
+    fn shortest_path(&self, a: K, b: K) -> Result<Option<Path<'_, T, K, E>>, DijkstraError> {
+        let s = self.index_of(&a).ok_or(DijkstraError::SourceNotFound)?;
+        let t = self.index_of(&b).ok_or(DijkstraError::TargetNotFound)?;
+        let (distances, parents) = dijkstra(&self.edges, s)?;
+        Ok(distances[t].map(|cost| (path_to(&self.nodes, &parents, t), cost)))
+    }
+
+    fn bellman_ford(
+        &self,
+        source: K,
+    ) -> Result<ShortestPaths<'_, T, K, E>, BellmanFordError<'_, T, K>> {
+        let s = self.index_of(&source).ok_or(BellmanFordError::SourceNotFound)?;
+        match bellman_ford(&self.edges, s) {
+            Ok((distances, parents)) => Ok(shortest_paths(&self.nodes, distances, parents)),
+            Err(cycle) => {
+                let cycle = cycle.into_iter().map(|i| &self.nodes[i]).collect();
+                Err(BellmanFordError::NegativeCycle(cycle))
+            }
+        }
+    }
+
+    fn astar(
+        &self,
+        start: K,
+        goal: K,
+        heuristic: impl Fn(&Node<T, K>) -> E,
+    ) -> Result<AStarSearch<'_, T, K, E>, AStarError> {
+        let s = self.index_of(&start).ok_or(AStarError::StartNotFound)?;
+        let t = self.index_of(&goal).ok_or(AStarError::GoalNotFound)?;
+        astar(&self.nodes, &self.edges, s, t, heuristic)
+    }
+}
+
+/// Heap entry: a node and a tentative distance to it. Ordered backwards so that `BinaryHeap` pops
+/// the closest one first
+pub(crate) struct Candidate<E>(pub E, pub usize);
+
+impl<E: PartialOrd> PartialEq for Candidate<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<E: PartialOrd> Eq for Candidate<E> {}
+
+impl<E: PartialOrd> PartialOrd for Candidate<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E: PartialOrd> Ord for Candidate<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0
+            .partial_cmp(&self.0)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+/// Distances from `source` and the previous node on each shortest path, by position. Nodes that
+/// can't be reached have neither
+fn dijkstra<E: Weight, S: Storage<E>>(
+    edges: &S,
+    source: usize,
+) -> Result<Search<E>, DijkstraError> {
+    let n = edges.order();
+    let mut distances: Vec<Option<E>> = vec![None; n];
+    let mut parents = vec![None; n];
+    let mut done = vec![false; n];
+    distances[source] = Some(E::default());
+    let mut heap = BinaryHeap::from([Candidate(E::default(), source)]);
+
+    while let Some(Candidate(d, x)) = heap.pop() {
+        if done[x] { continue; }
+        done[x] = true;
+
+        for (y, &w) in edges.neighbours(x) {
+            if w < E::default() {
+                return Err(DijkstraError::NegativeWeight);
+            }
+            let through_x = d + w;
+            if distances[y].is_none_or(|dy| through_x < dy) {
+                distances[y] = Some(through_x);
+                parents[y] = Some(x);
+                heap.push(Candidate(through_x, y));
+            }
+        }
+    }
+
+    Ok((distances, parents))
+}
+
+/// Same result as `dijkstra`, or the positions of a negative cycle, in order
+fn bellman_ford<E: Weight, S: Storage<E>>(edges: &S, source: usize) -> Result<Search<E>, Vec<usize>> {
+    let n = edges.order();
+    let mut distances: Vec<Option<E>> = vec![None; n];
+    let mut parents = vec![None; n];
+    distances[source] = Some(E::default());
+
+    // Without negative cycles, every shortest path has at most n - 1 edges, so n - 1 rounds are
+    // enough. If the n-th one still changes something, there's a cycle
+    for round in 0..n {
+        let mut changed = None;
+        for x in 0..n {
+            let Some(dx) = distances[x] else { continue };
+            for (y, &w) in edges.neighbours(x) {
+                if distances[y].is_none_or(|dy| dx + w < dy) {
+                    distances[y] = Some(dx + w);
+                    parents[y] = Some(x);
+                    changed = Some(y);
+                }
+            }
+        }
+
+        match changed {
+            None => break,
+            Some(y) if round == n - 1 => return Err(negative_cycle(&parents, y)),
+            Some(_) => {}
+        }
+    }
+
+    Ok((distances, parents))
+}
+
+/// `y` was updated on the last round, so going back n times from it is sure to end up inside the
+/// cycle. From there, it's a matter of going around once
+fn negative_cycle(parents: &[Option<usize>], mut y: usize) -> Vec<usize> {
+    for _ in 0..parents.len() {
+        y = parents[y].expect("every updated node has a parent");
+    }
+
+    let mut cycle = vec![y];
+    let mut x = parents[y].expect("every updated node has a parent");
+    while x != y {
+        cycle.push(x);
+        x = parents[x].expect("every updated node has a parent");
+    }
+    cycle.reverse();
+    cycle
+}
+
+fn floyd_warshall<E: Weight, S: Storage<E>>(edges: &S) -> Result<AllPairs<E>, FloydWarshallError> {
+    let n = edges.order();
+    let mut distances: Vec<Option<E>> = vec![None; n * n];
+    let mut next = vec![None; n * n];
+    for a in 0..n {
+        distances[coords_to_idx(a, a, n)] = Some(E::default());
+        next[coords_to_idx(a, a, n)] = Some(a);
+        for (b, &w) in edges.neighbours(a) {
+            distances[coords_to_idx(b, a, n)] = Some(w);
+            next[coords_to_idx(b, a, n)] = Some(b);
+        }
+    }
+
+    // After step k, the distances only go through the first k nodes
+    for k in 0..n {
+        for a in 0..n {
+            let Some(ak) = distances[coords_to_idx(k, a, n)] else { continue };
+            for b in 0..n {
+                let Some(kb) = distances[coords_to_idx(b, k, n)] else { continue };
+                let ab = coords_to_idx(b, a, n);
+                if distances[ab].is_none_or(|d| ak + kb < d) {
+                    // Going on around a negative cycle would only make the distances blow up
+                    if a == b && ak + kb < E::default() {
+                        return Err(FloydWarshallError::NegativeCycle);
+                    }
+                    distances[ab] = Some(ak + kb);
+                    next[ab] = next[coords_to_idx(k, a, n)];
+                }
+            }
+        }
+    }
+
+    Ok(AllPairs { n, distances, next })
+}
+
+fn astar<'a, T, K, E: Weight, S: Storage<E>>(
+    nodes: &'a [Node<T, K>],
+    edges: &S,
+    start: usize,
+    goal: usize,
+    heuristic: impl Fn(&Node<T, K>) -> E,
+) -> Result<AStarSearch<'a, T, K, E>, AStarError> {
+    let n = edges.order();
+    let mut estimates: Vec<Option<E>> = vec![None; n];
+    let mut estimate = |x: usize| *estimates[x].get_or_insert_with(|| heuristic(&nodes[x]));
+
+    let mut distances: Vec<Option<E>> = vec![None; n];
+    let mut parents = vec![None; n];
+    distances[start] = Some(E::default());
+    let mut heap = BinaryHeap::from([Candidate(estimate(start), start)]);
+    let mut expanded = 0;
+
+    while let Some(Candidate(f, x)) = heap.pop() {
+        let dx = distances[x].expect("queued nodes have a distance");
+        // Outdated entry, x was queued again with a better distance
+        if dx + estimate(x) < f { continue; }
+        if x == goal {
+            let path = path_to(nodes, &parents, goal);
+            return Ok(AStarSearch { path: Some((path, dx)), expanded });
+        }
+        expanded += 1;
+
+        for (y, &w) in edges.neighbours(x) {
+            if w < E::default() {
+                return Err(AStarError::NegativeWeight);
+            }
+            let through_x = dx + w;
+            if distances[y].is_none_or(|dy| through_x < dy) {
+                distances[y] = Some(through_x);
+                parents[y] = Some(x);
+                heap.push(Candidate(through_x + estimate(y), y));
+            }
+        }
+    }
+
+    Ok(AStarSearch { path: None, expanded })
+}
+
+/// A BFS from every node
+fn distance_matrix<E, S: Storage<E>>(edges: &S) -> Vec<Option<usize>> {
+    let n = edges.order();
+    let mut distances = vec![None; n * n];
+    for a in 0..n {
+        let row = &mut distances[a * n..(a + 1) * n];
+        row[a] = Some(0);
+        let mut queue = VecDeque::from([(a, 0)]);
+        while let Some((x, d)) = queue.pop_front() {
+            for (y, _) in edges.neighbours(x) {
+                if row[y].is_none() {
+                    row[y] = Some(d + 1);
+                    queue.push_back((y, d + 1));
+                }
+            }
+        }
+    }
+    distances
+}
+
+/// Puts names to the result of a single source search
+pub(crate) fn shortest_paths<'a, T, K: Key, E>(
+    nodes: &'a [Node<T, K>],
+    distances: Vec<Option<E>>,
+    parents: Vec<Option<usize>>,
+) -> ShortestPaths<'a, T, K, E> {
+    let mut sp = ShortestPaths { distances: HashMap::new(), predecessors: HashMap::new() };
+    for (i, d) in distances.into_iter().enumerate() {
+        if let Some(d) = d {
+            sp.distances.insert(nodes[i].name.clone(), d);
+            sp.predecessors.insert(nodes[i].name.clone(), parents[i].map(|p| &nodes[p]));
+        }
+    }
+    sp
+}
+
+/// Follows `parents` back from `target` to the root, then returns the nodes from the root onwards
+pub(crate) fn path_to<'a, T, K>(
+    nodes: &'a [Node<T, K>],
+    parents: &[Option<usize>],
+    target: usize,
+) -> Vec<&'a Node<T, K>> {
+    let mut path = vec![&nodes[target]];
+    let mut x = target;
+    while let Some(p) = parents[x] {
+        path.push(&nodes[p]);
+        x = p;
+    }
+    path.reverse();
+    path
+}
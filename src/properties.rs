@@ -1,29 +1,423 @@
-use std::collections::HashMap;
-use std::hash::Hash;
+use std::collections::{HashMap, VecDeque};
 
-use crate::{Graph, Node};
+use crate::{base::Base, AdjMatrix, DisjointSet, Edge, Graph, Key, Node, Storage};
 
-impl<T> Graph<T> {
-    pub fn is_tree(&self) -> bool where T: Eq + Hash {
-        let Some(v) = self.nodes.get(0) else { return true; };
+/// The two sides of a bipartite graph. Every edge goes from one side to the other
+#[derive(Debug, Clone)]
+pub struct Bipartition<'a, T, K = char> {
+    pub left: Vec<&'a Node<T, K>>,
+    pub right: Vec<&'a Node<T, K>>,
+}
+
+/// Proof that a graph isn't bipartite: a cycle with an odd amount of nodes, in order
+pub type OddCycle<'a, T, K = char> = Vec<&'a Node<T, K>>;
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
+    /// Connected and without cycles
+    pub fn is_tree(&self) -> bool {
+        self.is_connected() && self.is_forest()
+    }
+
+    /// Without cycles. Each of its components is a tree
+    pub fn is_forest(&self) -> bool {
+        // Every component with c nodes needs at least c - 1 edges, exactly that if it's a tree
+        self.edge_count() + self.component_count() == self.base.nodes.len()
+    }
+
+    /// The empty graph counts as connected
+    pub fn is_connected(&self) -> bool {
+        self.component_count() <= 1
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components().len()
+    }
+
+    /// The nodes of each component, in the order `dfs` would visit them. Components are sorted by
+    /// the position of their first node in the graph
+    pub fn connected_components(&self) -> Vec<Vec<&Node<T, K>>> {
+        self.components()
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| &self.base.nodes[i]).collect())
+            .collect()
+    }
+
+    /// Same components as `connected_components`, found by joining the ends of every edge in a
+    /// `DisjointSet` instead of searching. Nodes in each component keep their order in the graph
+    pub fn components_union_find(&self) -> Vec<Vec<&Node<T, K>>> {
+        let n = self.base.nodes.len();
+        let mut sets = DisjointSet::new(n);
+        for a in 0..n {
+            for (b, _) in self.base.edges.neighbours(a) {
+                sets.union(a, b);
+            }
+        }
+
+        // Components are numbered as their first node shows up
+        let mut numbers: Vec<Option<usize>> = vec![None; n];
+        let mut components: Vec<Vec<&Node<T, K>>> = Vec::new();
+        for x in 0..n {
+            let root = sets.find(x);
+            let c = *numbers[root].get_or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[c].push(&self.base.nodes[x]);
+        }
+        components
+    }
+
+    /// Same as `connected_components`, by position
+    pub(crate) fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.base.nodes.len()];
+        let mut components = Vec::new();
+
+        for root in 0..self.base.nodes.len() {
+            if seen[root] { continue; }
+
+            seen[root] = true;
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            while let Some(x) = stack.pop() {
+                for (y, _) in self.base.edges.neighbours(x) {
+                    if !seen[y] {
+                        seen[y] = true;
+                        component.push(y);
+                        stack.push(y);
+                    }
+                }
+            }
+            components.push(component);
+        }
 
-        let mut w = vec![v];   
-        let mut stack = vec![v];
-        let mut parents: HashMap<&Node<T>, Option<&Node<T>>> = HashMap::new();
+        components
+    }
+
+    /// One of the graph's cycles, if it has any: consecutive nodes are adjacent, and so are the
+    /// last and the first
+    pub fn find_cycle(&self) -> Option<Vec<&Node<T, K>>> {
+        let mut seen = vec![false; self.base.nodes.len()];
+        let mut parents: Vec<Option<usize>> = vec![None; self.base.nodes.len()];
+
+        for root in 0..self.base.nodes.len() {
+            if seen[root] { continue; }
 
-        while let Some(x) = stack.pop() {
-            for y in self.adjacent_nodes(x.name).unwrap() {
-                if let Some(Some(p)) = parents.get(x) {
-                    if p == &y { continue; }
+            seen[root] = true;
+            let mut stack = vec![root];
+            while let Some(x) = stack.pop() {
+                for (y, _) in self.base.edges.neighbours(x) {
+                    if parents[x] == Some(y) { continue; }
+                    if seen[y] {
+                        return Some(tree_cycle(&parents, x, y).into_iter().map(|i| &self.base.nodes[i]).collect());
+                    }
+
+                    seen[y] = true;
+                    stack.push(y);
+                    parents[y] = Some(x);
                 }
-                if w.contains(&y) { return false; }
+            }
+        }
 
-                w.push(y);
-                stack.push(y);
-                parents.insert(y, Some(x));
+        None
+    }
+
+    /// Length of the shortest cycle, None if there are no cycles at all
+    pub fn girth(&self) -> Option<usize> {
+        let n = self.base.nodes.len();
+        let mut best: Option<usize> = None;
+
+        // The shortest cycle through the root is found by the BFS that starts from it
+        for root in 0..n {
+            let mut distances: Vec<Option<usize>> = vec![None; n];
+            let mut parents: Vec<Option<usize>> = vec![None; n];
+            distances[root] = Some(0);
+            let mut queue = VecDeque::from([root]);
+
+            while let Some(x) = queue.pop_front() {
+                let dx = distances[x].unwrap();
+                if best.is_some_and(|b| 2 * dx + 1 >= b) { break; }
+
+                for (y, _) in self.base.edges.neighbours(x) {
+                    match distances[y] {
+                        None => {
+                            distances[y] = Some(dx + 1);
+                            parents[y] = Some(x);
+                            queue.push_back(y);
+                        }
+                        Some(dy) if parents[x] != Some(y) => {
+                            let len = dx + dy + 1;
+                            best = Some(best.map_or(len, |b| b.min(len)));
+                        }
+                        Some(_) => {}
+                    }
+                }
             }
         }
 
-        true
+        best
     }
+
+    /// Every edge is stored both ways
+    fn edge_count(&self) -> usize {
+        (0..self.base.nodes.len()).map(|i| self.base.edges.neighbours(i).count()).sum::<usize>() / 2
+    }
+
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartition().is_ok()
+    }
+
+    /// Splits the graph in two sides such that no edge is inside a side. Every component is
+    /// checked, isolated nodes end up on the left.
+    ///
+    /// Errors with an odd cycle if there is no such split: consecutive nodes are adjacent, and
+    /// so are the last and the first
+    pub fn bipartition(&self) -> Result<Bipartition<'_, T, K>, OddCycle<'_, T, K>> {
+        // false means left, true means right
+        let mut colors: Vec<Option<bool>> = vec![None; self.base.nodes.len()];
+        let mut parents: Vec<Option<usize>> = vec![None; self.base.nodes.len()];
+        let mut parts = Bipartition { left: vec![], right: vec![] };
+
+        for root in 0..self.base.nodes.len() {
+            if colors[root].is_some() { continue; }
+
+            colors[root] = Some(false);
+            parts.left.push(&self.base.nodes[root]);
+            let mut queue = VecDeque::from([root]);
+
+            while let Some(x) = queue.pop_front() {
+                let color = colors[x] == Some(true);
+                for (y, _) in self.base.edges.neighbours(x) {
+                    match colors[y] {
+                        None => {
+                            colors[y] = Some(!color);
+                            parents[y] = Some(x);
+                            if color { parts.left.push(&self.base.nodes[y]) } else { parts.right.push(&self.base.nodes[y]) }
+                            queue.push_back(y);
+                        }
+                        Some(c) if c == color => {
+                            return Err(tree_cycle(&parents, x, y).into_iter().map(|i| &self.base.nodes[i]).collect());
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(parts)
+    }
+
+    /// Nodes whose removal leaves more components than there were, in the order of the graph
+    pub fn articulation_points(&self) -> Vec<&Node<T, K>> {
+        let ll = self.low_links();
+        let mut children = vec![0; self.base.nodes.len()];
+        let mut cut = vec![false; self.base.nodes.len()];
+
+        for &y in &ll.order {
+            let Some(x) = ll.parents[y] else { continue };
+            children[x] += 1;
+            // Nothing under y reaches above x without going through it
+            if ll.parents[x].is_some() && ll.low[y] >= ll.discovery[x] {
+                cut[x] = true;
+            }
+        }
+        // Roots don't have anything above them, they only matter if they hold two subtrees apart
+        for (x, c) in children.into_iter().enumerate() {
+            if ll.parents[x].is_none() && c >= 2 {
+                cut[x] = true;
+            }
+        }
+
+        (0..self.base.nodes.len()).filter(|&x| cut[x]).map(|x| &self.base.nodes[x]).collect()
+    }
+
+    /// Edges whose removal leaves more components than there were. Each one goes from parent to
+    /// child in a DFS tree, in the order the children were found
+    pub fn bridges(&self) -> Vec<Edge<'_, T, K>> {
+        let ll = self.low_links();
+        ll.order
+            .iter()
+            .filter_map(|&y| {
+                let x = ll.parents[y]?;
+                // Nothing under y reaches x or above, so the edge is the only way in
+                (ll.low[y] > ll.discovery[x]).then(|| (&self.base.nodes[x], &self.base.nodes[y]))
+            })
+            .collect()
+    }
+
+    /// The blocks of the graph: maximal pieces that can't be split by removing a single node. Each
+    /// one is given by its edges, so nodes without any don't show up. Blocks are listed in the
+    /// order a DFS finishes them, and two of them share at most one node, an articulation point
+    pub fn biconnected_components(&self) -> Vec<Vec<Edge<'_, T, K>>> {
+        self.low_links()
+            .blocks
+            .into_iter()
+            .map(|b| {
+                b.into_iter().map(|(x, y)| (&self.base.nodes[x], &self.base.nodes[y])).collect()
+            })
+            .collect()
+    }
+
+    /// Maximal sets of nodes that stay connected after removing any single edge. These are the
+    /// components left after removing every bridge, so every node is in exactly one of them
+    pub fn two_edge_connected_components(&self) -> Vec<Vec<&Node<T, K>>> {
+        let ll = self.low_links();
+        let mut numbers = vec![0; self.base.nodes.len()];
+        let mut components: Vec<Vec<&Node<T, K>>> = Vec::new();
+
+        // Parents are found before their children, so their number is already known
+        for &y in &ll.order {
+            let same_as_parent = ll.parents[y].filter(|&x| ll.low[y] <= ll.discovery[x]);
+            numbers[y] = match same_as_parent {
+                Some(x) => numbers[x],
+                None => {
+                    components.push(Vec::new());
+                    components.len() - 1
+                }
+            };
+            components[numbers[y]].push(&self.base.nodes[y]);
+        }
+        components
+    }
+
+    /// Tree with a node for each block and one for each articulation point, joining every
+    /// articulation point to the blocks it's in. Blocks are numbered first, in the order of
+    /// `biconnected_components`, and articulation points after them, in the order of
+    /// `articulation_points`. It's a forest if the graph isn't connected
+    pub fn block_cut_tree(&self) -> Graph<BlockCutNode<'_, T, K>, usize> {
+        let blocks = self.low_links().blocks;
+        let cuts = self.articulation_points();
+        let b = blocks.len();
+        let numbers: HashMap<&K, usize> =
+            cuts.iter().enumerate().map(|(i, cut)| (&cut.name, b + i)).collect();
+
+        let mut nodes = Vec::with_capacity(b + cuts.len());
+        let mut edges = Vec::new();
+        for (i, block) in blocks.into_iter().enumerate() {
+            let mut members: Vec<usize> = block.into_iter().flat_map(|(x, y)| [x, y]).collect();
+            members.sort_unstable();
+            members.dedup();
+
+            let members: Vec<&Node<T, K>> =
+                members.into_iter().map(|x| &self.base.nodes[x]).collect();
+            edges.extend(members.iter().filter_map(|n| Some((i, *numbers.get(&n.name)?))));
+            nodes.push(Node::new(i, BlockCutNode::Block(members)));
+        }
+        for (i, cut) in cuts.into_iter().enumerate() {
+            nodes.push(Node::new(b + i, BlockCutNode::Cut(cut)));
+        }
+
+        // Every node's name is also its position, so the edges can go straight into the storage
+        let k = nodes.len();
+        let mut tree = Base::new(nodes, AdjMatrix::empty(k));
+        for (block, cut) in edges {
+            tree.edges.set(block, cut, Some(()));
+            tree.edges.set(cut, block, Some(()));
+        }
+        Graph { base: tree }
+    }
+
+    /// Tarjan's low-links, with a DFS that starts from every node that hasn't been reached yet
+    fn low_links(&self) -> LowLinks {
+        let n = self.base.nodes.len();
+        let mut ll = LowLinks {
+            discovery: vec![usize::MAX; n],
+            low: vec![usize::MAX; n],
+            parents: vec![None; n],
+            order: Vec::with_capacity(n),
+            blocks: Vec::new(),
+        };
+
+        for root in 0..n {
+            if ll.discovery[root] != usize::MAX { continue; }
+
+            ll.discover(root, None);
+            let mut edges = Vec::new();
+            // Each node keeps its own neighbours iterator, so going back to it resumes where it
+            // left off, as a recursive DFS would
+            let mut stack = vec![(root, self.base.edges.neighbours(root))];
+            while let Some((x, neighbours)) = stack.last_mut() {
+                let x = *x;
+                match neighbours.next() {
+                    Some((y, _)) if ll.discovery[y] == usize::MAX => {
+                        ll.discover(y, Some(x));
+                        edges.push((x, y));
+                        stack.push((y, self.base.edges.neighbours(y)));
+                    }
+                    Some((y, _)) => {
+                        // Back edges are seen from both ends, they're kept from the lower one
+                        if ll.parents[x] != Some(y) && ll.discovery[y] < ll.discovery[x] {
+                            ll.low[x] = ll.low[x].min(ll.discovery[y]);
+                            edges.push((x, y));
+                        }
+                    }
+                    None => {
+                        stack.pop();
+                        let Some(p) = ll.parents[x] else { continue };
+                        ll.low[p] = ll.low[p].min(ll.low[x]);
+                        // p cuts x's subtree off: the edges since p -> x make up a block
+                        if ll.low[x] >= ll.discovery[p] {
+                            let start = edges.iter().rposition(|&e| e == (p, x)).unwrap();
+                            ll.blocks.push(edges.split_off(start));
+                        }
+                    }
+                }
+            }
+        }
+
+        ll
+    }
+}
+
+/// A node of the block-cut tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockCutNode<'a, T, K = char> {
+    /// The nodes of a block, in the order of the graph
+    Block(Vec<&'a Node<T, K>>),
+    /// An articulation point
+    Cut(&'a Node<T, K>),
+}
+
+/// What a DFS finds out about how the nodes hang from each other, by position
+struct LowLinks {
+    /// When each node was found, counting from 0
+    discovery: Vec<usize>,
+    /// Earliest discovery time reachable from a node's subtree through a single non-tree edge
+    low: Vec<usize>,
+    /// Parent in the DFS tree, None for the roots
+    parents: Vec<Option<usize>>,
+    /// Nodes in the order they were found
+    order: Vec<usize>,
+    /// Edges of each biconnected component, as (parent, child) for tree edges and (descendant,
+    /// ancestor) for the rest
+    blocks: Vec<Vec<(usize, usize)>>,
+}
+
+impl LowLinks {
+    fn discover(&mut self, x: usize, parent: Option<usize>) {
+        self.discovery[x] = self.order.len();
+        self.low[x] = self.order.len();
+        self.parents[x] = parent;
+        self.order.push(x);
+    }
+}
+
+/// Given two adjacent nodes of the same search tree (that aren't parent and child), walks both up
+/// to their common ancestor. The result goes from `x` to the ancestor and then back down to `y`,
+/// closing a cycle through the edge between them
+fn tree_cycle(parents: &[Option<usize>], x: usize, y: usize) -> Vec<usize> {
+    let mut up_from_x = vec![x];
+    while let Some(p) = parents[*up_from_x.last().unwrap()] {
+        up_from_x.push(p);
+    }
+
+    let mut up_from_y = vec![y];
+    while !up_from_x.contains(up_from_y.last().unwrap()) {
+        up_from_y.push(parents[*up_from_y.last().unwrap()].unwrap());
+    }
+
+    let ancestor = up_from_y.pop().unwrap();
+    let mut cycle: Vec<usize> = up_from_x.into_iter().take_while(|n| *n != ancestor).collect();
+    cycle.push(ancestor);
+    cycle.extend(up_from_y.into_iter().rev());
+    cycle
 }
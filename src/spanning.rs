@@ -0,0 +1,92 @@
+// Minimum spanning trees, or forests when the graph isn't connected
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{
+    base::Base, paths::Candidate, utils::DisjointSet, Graph, Key, Storage, Weight, WeightedEdge,
+};
+
+impl<T, K: Key, E: Weight, S: Storage<E>> Graph<T, K, E, S> {
+    /// Edges of a minimum spanning forest: a minimum spanning tree of each component. Same as
+    /// `kruskal`
+    pub fn minimum_spanning_tree(&self) -> Vec<WeightedEdge<'_, T, K, E>> {
+        self.kruskal()
+    }
+
+    /// Same as `minimum_spanning_tree`, as a new graph with the same nodes and only the edges of
+    /// the forest
+    pub fn minimum_spanning_tree_graph(&self) -> Graph<T, K, E, S>
+    where
+        T: Clone,
+    {
+        // Same nodes in the same positions, so the original index is good for the forest too
+        let mut forest = Base::new(self.base.nodes.clone(), S::empty(self.base.nodes.len()));
+        for (a, b, &w) in self.kruskal() {
+            let a = self.base.index[&a.name];
+            let b = self.base.index[&b.name];
+            forest.edges.set(a, b, Some(w));
+            forest.edges.set(b, a, Some(w));
+        }
+        Graph { base: forest }
+    }
+
+    /// Minimum spanning forest, going through the edges from cheapest to most expensive and
+    /// keeping those that don't close a cycle. Edges come out in that order
+    pub fn kruskal(&self) -> Vec<WeightedEdge<'_, T, K, E>> {
+        let n = self.base.nodes.len();
+        // Each edge is stored both ways, only one of them is needed
+        let mut edges: Vec<(usize, usize, &E)> = (0..n)
+            .flat_map(|a| {
+                self.base
+                    .edges
+                    .neighbours(a)
+                    .filter(move |&(b, _)| a < b)
+                    .map(move |(b, w)| (a, b, w))
+            })
+            .collect();
+        edges.sort_by(|x, y| x.2.partial_cmp(y.2).unwrap_or(Ordering::Equal));
+
+        let mut sets = DisjointSet::new(n);
+        let mut forest = Vec::new();
+        for (a, b, w) in edges {
+            if sets.union(a, b) {
+                forest.push((&self.base.nodes[a], &self.base.nodes[b], w));
+                if forest.len() + 1 == n { break; }
+            }
+        }
+        forest
+    }
+
+    /// Minimum spanning forest, growing a tree from the first node of each component by always
+    /// adding the cheapest edge that reaches a new node. Edges come out in that order, as (node
+    /// already in the tree, new node)
+    pub fn prim(&self) -> Vec<WeightedEdge<'_, T, K, E>> {
+        let n = self.base.nodes.len();
+        let mut in_tree = vec![false; n];
+        // Cheapest edge known so far from the tree to each node, as (value, other end)
+        let mut best: Vec<Option<(E, usize)>> = vec![None; n];
+        let mut forest = Vec::new();
+
+        for root in 0..n {
+            if in_tree[root] { continue; }
+
+            let mut heap = BinaryHeap::from([Candidate(E::default(), root)]);
+            while let Some(Candidate(_, x)) = heap.pop() {
+                if in_tree[x] { continue; }
+                in_tree[x] = true;
+                if let Some((_, p)) = best[x] {
+                    let w = self.base.edges.weight(p, x).expect("best only holds existing edges");
+                    forest.push((&self.base.nodes[p], &self.base.nodes[x], w));
+                }
+
+                for (y, &w) in self.base.edges.neighbours(x) {
+                    if !in_tree[y] && best[y].is_none_or(|(b, _)| w < b) {
+                        best[y] = Some((w, x));
+                        heap.push(Candidate(w, y));
+                    }
+                }
+            }
+        }
+        forest
+    }
+}
@@ -0,0 +1,223 @@
+// Ways of keeping track of the edges of a graph. The graph itself only deals with the nodes and
+// asks its storage about everything else, by position
+
+use std::{
+    fmt::{self, Debug},
+    mem,
+};
+
+use crate::{
+    utils::{coords_to_idx, idx_to_coords},
+    FromMatrixError,
+};
+
+/// How a graph stores its edges. Nodes are referred to by their position in the graph, and edges
+/// are directed: undirected graphs keep every edge both ways.
+///
+/// All methods assume the positions they get are in bounds
+pub trait Storage<E> {
+    /// `n` nodes and no edges
+    fn empty(n: usize) -> Self;
+
+    /// Amount of nodes
+    fn order(&self) -> usize;
+
+    /// Value of the edge a -> b, if there is one
+    fn weight(&self, a: usize, b: usize) -> Option<&E>;
+
+    /// Sets the value of the edge a -> b, or removes it with `None`. Returns the previous value
+    fn set(&mut self, a: usize, b: usize, value: Option<E>) -> Option<E>;
+
+    /// Every edge leaving a, as (other end, value), sorted by the position of the other end
+    fn neighbours<'a>(&'a self, a: usize) -> impl Iterator<Item = (usize, &'a E)>
+    where
+        E: 'a;
+
+    /// Adds an isolated node at the end
+    fn push_node(&mut self);
+
+    /// Removes x along with its edges. Every node after it moves one position back
+    fn remove_node(&mut self, x: usize);
+}
+
+/// Dense storage, best for small or dense graphs: n² entries, row a and column b being the edge
+/// a -> b. `Some` holds the edge's value, `None` means there's no edge
+///
+/// There's room for more nodes than there are, so that adding them one at a time only moves the
+/// whole matrix when it's full, doubling its size
+#[derive(Clone)]
+pub struct AdjMatrix<E = ()> {
+    /// Rows are `cap` entries long. Everything past the first n rows and columns is `None`
+    values: Vec<Option<E>>,
+    n: usize,
+    cap: usize,
+}
+
+impl<E> AdjMatrix<E> {
+    fn row(&self, a: usize) -> &[Option<E>] {
+        &self.values[a * self.cap..a * self.cap + self.n]
+    }
+}
+
+// The spare room is left out of both comparisons and debug output
+impl<E: PartialEq> PartialEq for AdjMatrix<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && (0..self.n).all(|a| self.row(a) == other.row(a))
+    }
+}
+
+impl<E: Eq> Eq for AdjMatrix<E> {}
+
+impl<E: Debug> Debug for AdjMatrix<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries((0..self.n).map(|a| self.row(a))).finish()
+    }
+}
+
+impl<E> Storage<E> for AdjMatrix<E> {
+    fn empty(n: usize) -> Self {
+        AdjMatrix {
+            values: (0..n * n).map(|_| None).collect(),
+            n,
+            cap: n,
+        }
+    }
+
+    fn order(&self) -> usize {
+        self.n
+    }
+
+    fn weight(&self, a: usize, b: usize) -> Option<&E> {
+        self.values[coords_to_idx(b, a, self.cap)].as_ref()
+    }
+
+    fn set(&mut self, a: usize, b: usize, value: Option<E>) -> Option<E> {
+        mem::replace(&mut self.values[coords_to_idx(b, a, self.cap)], value)
+    }
+
+    fn neighbours<'a>(&'a self, a: usize) -> impl Iterator<Item = (usize, &'a E)>
+    where
+        E: 'a,
+    {
+        self.row(a).iter().enumerate().filter_map(|(b, w)| Some((b, w.as_ref()?)))
+    }
+
+    /// Adds a new, isolated, last row and column. Only moves the matrix if there's no room left
+    fn push_node(&mut self) {
+        if self.n == self.cap {
+            let cap = (2 * self.cap).max(1);
+            let mut bigger: Vec<Option<E>> = (0..cap * cap).map(|_| None).collect();
+            for (i, v) in mem::take(&mut self.values).into_iter().enumerate() {
+                let (x, y) = idx_to_coords(i, self.cap);
+                bigger[coords_to_idx(x, y, cap)] = v;
+            }
+            self.values = bigger;
+            self.cap = cap;
+        }
+        self.n += 1;
+    }
+
+    /// Removes x's row and column, moving the rest up and to the left
+    fn remove_node(&mut self, x: usize) {
+        let (n, cap) = (self.n, self.cap);
+        for a in 0..n {
+            let row = &mut self.values[a * cap..a * cap + n];
+            row[x..].rotate_left(1);
+            row[n - 1] = None;
+        }
+        self.values[x * cap..n * cap].rotate_left(cap);
+        self.values[(n - 1) * cap..n * cap].fill_with(|| None);
+        self.n -= 1;
+    }
+}
+
+/// Sparse storage, best for big graphs with few edges per node: each node keeps a list of its
+/// edges, sorted by the other end. Memory is linear in the amount of nodes and edges, and looking
+/// at a node's edges takes time proportional to its degree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdjList<E = ()> {
+    lists: Vec<Vec<(usize, E)>>,
+}
+
+impl<E> AdjList<E> {
+    fn find(&self, a: usize, b: usize) -> Result<usize, usize> {
+        self.lists[a].binary_search_by_key(&b, |(x, _)| *x)
+    }
+}
+
+impl<E> Storage<E> for AdjList<E> {
+    fn empty(n: usize) -> Self {
+        AdjList {
+            lists: (0..n).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    fn order(&self) -> usize {
+        self.lists.len()
+    }
+
+    fn weight(&self, a: usize, b: usize) -> Option<&E> {
+        let i = self.find(a, b).ok()?;
+        Some(&self.lists[a][i].1)
+    }
+
+    fn set(&mut self, a: usize, b: usize, value: Option<E>) -> Option<E> {
+        match (self.find(a, b), value) {
+            (Ok(i), Some(w)) => Some(mem::replace(&mut self.lists[a][i].1, w)),
+            (Ok(i), None) => Some(self.lists[a].remove(i).1),
+            (Err(i), Some(w)) => {
+                self.lists[a].insert(i, (b, w));
+                None
+            }
+            (Err(_), None) => None,
+        }
+    }
+
+    fn neighbours<'a>(&'a self, a: usize) -> impl Iterator<Item = (usize, &'a E)>
+    where
+        E: 'a,
+    {
+        self.lists[a].iter().map(|(b, w)| (*b, w))
+    }
+
+    fn push_node(&mut self) {
+        self.lists.push(Vec::new());
+    }
+
+    fn remove_node(&mut self, x: usize) {
+        self.lists.remove(x);
+        for list in &mut self.lists {
+            list.retain(|(b, _)| *b != x);
+            for (b, _) in list {
+                if *b > x {
+                    *b -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// Builds a storage out of a matrix, row a and column b being the edge a -> b. Symmetry is up to
+/// the caller
+pub(crate) fn from_matrix<E, S: Storage<E>, const N: usize>(
+    m: [[Option<E>; N]; N],
+) -> Result<S, FromMatrixError> {
+    if (0..N).any(|i| m[i][i].is_some()) {
+        return Err(FromMatrixError::NonEmptyDiagonal);
+    }
+
+    let mut s = S::empty(N);
+    for (a, row) in m.into_iter().enumerate() {
+        for (b, w) in row.into_iter().enumerate() {
+            if w.is_some() {
+                s.set(a, b, w);
+            }
+        }
+    }
+    Ok(s)
+}
+
+/// Whether every edge a -> b has a matching b -> a, with the same value
+pub(crate) fn is_symmetric<E: PartialEq, S: Storage<E>>(s: &S) -> bool {
+    (0..s.order()).all(|a| s.neighbours(a).all(|(b, w)| s.weight(b, a) == Some(w)))
+}
@@ -1,8 +1,10 @@
 // Contains helper functions that traverse the graph, like BFS and DFS
 
+use std::collections::{HashMap, VecDeque};
+
 use thiserror::Error;
 
-use crate::{Graph, Node};
+use crate::{Edge, Graph, Key, Node, Storage};
 
 #[derive(Debug, Error)]
 pub enum DFSError {
@@ -10,25 +12,52 @@ pub enum DFSError {
     VertexNotFound,
 }
 
-impl<T> Graph<T> {
+#[derive(Debug, Error)]
+pub enum BFSError {
+    #[error("starting vertex was not found in the graph")]
+    VertexNotFound,
+}
+
+/// Nodes in the order a DFS visits them, and the edges it goes through as (parent, child)
+pub type DFSWalk<'a, T, K = char> = (Vec<&'a Node<T, K>>, Vec<Edge<'a, T, K>>);
+
+/// Everything a BFS finds out about the component of its starting node. Both maps are keyed by
+/// node name and only contain the nodes that were reached
+#[derive(Debug, Clone)]
+pub struct BFSTree<'a, T, K = char> {
+    /// Nodes in the order they were visited
+    pub order: Vec<&'a Node<T, K>>,
+    /// The edges of the BFS tree, as (parent, child)
+    pub edges: Vec<Edge<'a, T, K>>,
+    /// Amount of hops from the starting node
+    pub distances: HashMap<K, usize>,
+    /// Parent in the BFS tree, None for the starting node
+    pub parents: HashMap<K, Option<&'a Node<T, K>>>,
+}
+
+impl<T, K: Key, E, S: Storage<E>> Graph<T, K, E, S> {
     pub fn dfs<'a, 'b>(
         &'a self,
-        v: &'b Node<T>,
-    ) -> Result<(Vec<&'a Node<T>>, Vec<(&'a Node<T>, &'a Node<T>)>), DFSError>
+        v: &'b Node<T, K>,
+    ) -> Result<DFSWalk<'a, T, K>, DFSError>
     where
         'b: 'a,
-        T: PartialEq
     {
+        let start = self.index_of(&v.name).ok_or(DFSError::VertexNotFound)?;
+        let mut seen = vec![false; self.base.nodes.len()];
+        seen[start] = true;
+
         let mut w = vec![v];
-        let mut stack = vec![v];
+        let mut stack = vec![(start, v)];
         let mut arestes = vec![];
 
-        while let Some(x) = stack.pop() {
-            for y in self.adjacent_nodes(x.name).ok_or(DFSError::VertexNotFound)? {
-                if !w.contains(&y)
-                {
+        while let Some((i, x)) = stack.pop() {
+            for (j, _) in self.base.edges.neighbours(i) {
+                if !seen[j] {
+                    seen[j] = true;
+                    let y = &self.base.nodes[j];
                     w.push(y);
-                    stack.push(y);
+                    stack.push((j, y));
                     arestes.push((x, y));
                 }
             }
@@ -36,4 +65,35 @@ impl<T> Graph<T> {
 
         Ok((w, arestes))
     }
+
+    pub fn bfs<'a, 'b>(&'a self, v: &'b Node<T, K>) -> Result<BFSTree<'a, T, K>, BFSError>
+    where
+        'b: 'a,
+    {
+        let start = self.index_of(&v.name).ok_or(BFSError::VertexNotFound)?;
+        let mut seen = vec![false; self.base.nodes.len()];
+        seen[start] = true;
+
+        let mut order = vec![v];
+        let mut queue = VecDeque::from([(start, v, 0)]);
+        let mut edges = vec![];
+        let mut distances = HashMap::from([(v.name.clone(), 0)]);
+        let mut parents = HashMap::from([(v.name.clone(), None)]);
+
+        while let Some((i, x, d)) = queue.pop_front() {
+            for (j, _) in self.base.edges.neighbours(i) {
+                if !seen[j] {
+                    seen[j] = true;
+                    let y = &self.base.nodes[j];
+                    distances.insert(y.name.clone(), d + 1);
+                    parents.insert(y.name.clone(), Some(x));
+                    order.push(y);
+                    queue.push_back((j, y, d + 1));
+                    edges.push((x, y));
+                }
+            }
+        }
+
+        Ok(BFSTree { order, edges, distances, parents })
+    }
 }
@@ -1,8 +1,10 @@
 use std::{
-    collections::{BTreeSet, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     hash::Hash,
 };
 
+use crate::Key;
+
 pub fn coords_to_idx(x: usize, y: usize, w: usize) -> usize {
     x + w * y
 }
@@ -38,3 +40,138 @@ where
         v.remove(i);
     }
 }
+
+/// Union-find over the numbers 0..n, such as node positions, with union by rank and path
+/// compression. Both `find` and `union` take nearly constant amortized time
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sets: usize,
+}
+
+impl DisjointSet {
+    /// Every element on its own set
+    pub fn new(n: usize) -> Self {
+        DisjointSet { parents: (0..n).collect(), ranks: vec![0; n], sets: n }
+    }
+
+    /// Amount of elements
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Amount of sets
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Adds a new element on its own set and returns it
+    pub fn push(&mut self) -> usize {
+        self.parents.push(self.parents.len());
+        self.ranks.push(0);
+        self.sets += 1;
+        self.parents.len() - 1
+    }
+
+    /// Representative of x's set
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Everything on the way now points straight to the root
+        let mut x = x;
+        while self.parents[x] != root {
+            let next = self.parents[x];
+            self.parents[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Joins the sets of a and b. Returns false if they already were the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (small, big) = if self.ranks[a] < self.ranks[b] { (a, b) } else { (b, a) };
+        self.parents[small] = big;
+        if self.ranks[small] == self.ranks[big] {
+            self.ranks[big] += 1;
+        }
+        self.sets -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+/// Same as `DisjointSet`, with elements referred to by name, like the nodes of a graph. Every
+/// method returns None when given a name it doesn't know
+#[derive(Debug, Clone)]
+pub struct KeyedDisjointSet<K> {
+    set: DisjointSet,
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+}
+
+impl<K: Key> KeyedDisjointSet<K> {
+    /// Every name on its own set. Repeated names are only added once
+    pub fn new(keys: impl IntoIterator<Item = K>) -> Self {
+        let mut s =
+            KeyedDisjointSet { set: DisjointSet::new(0), keys: Vec::new(), index: HashMap::new() };
+        for k in keys {
+            s.insert(k);
+        }
+        s
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.set.set_count()
+    }
+
+    /// Adds a name on its own set. Returns false if it was already there
+    pub fn insert(&mut self, k: K) -> bool {
+        if self.index.contains_key(&k) {
+            return false;
+        }
+        self.index.insert(k.clone(), self.set.push());
+        self.keys.push(k);
+        true
+    }
+
+    /// Representative of k's set
+    pub fn find(&mut self, k: &K) -> Option<K> {
+        let root = self.set.find(*self.index.get(k)?);
+        Some(self.keys[root].clone())
+    }
+
+    /// Joins the sets of a and b. Returns false if they already were the same set
+    pub fn union(&mut self, a: &K, b: &K) -> Option<bool> {
+        let (a, b) = (*self.index.get(a)?, *self.index.get(b)?);
+        Some(self.set.union(a, b))
+    }
+
+    pub fn same_set(&mut self, a: &K, b: &K) -> Option<bool> {
+        let (a, b) = (*self.index.get(a)?, *self.index.get(b)?);
+        Some(self.set.same_set(a, b))
+    }
+}
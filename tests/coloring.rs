@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use graphs::{ColoringError, Graph, Node};
+
+fn is_proper(g: &Graph<(), u32>, colors: &HashMap<u32, usize>) -> bool {
+    g.nodes().len() == colors.len()
+        && g.nodes().iter().all(|x| {
+            let mut around = g.adjacent_nodes(x.name).unwrap().into_iter();
+            around.all(|y| colors[&x.name] != colors[&y.name])
+        })
+}
+
+fn count(colors: &HashMap<u32, usize>) -> usize {
+    colors.values().max().map_or(0, |m| m + 1)
+}
+
+/// Nodes 0..n and the given edges
+fn graph(n: u32, edges: &[(u32, u32)]) -> Graph<(), u32> {
+    let mut g = Graph::empty();
+    for i in 0..n {
+        g.add_node(Node::new(i, ())).unwrap();
+    }
+    for &(a, b) in edges {
+        g.add_edge(a, b).unwrap();
+    }
+    g
+}
+
+#[test]
+fn colorings() {
+    // Crown graph: two rows of 4, each node adjacent to the other row except the one right across.
+    // Bipartite, but greedy in the order 0, 4, 1, 5, ... needs 4 colours
+    let mut edges = vec![];
+    for i in 0..4 {
+        for j in 0..4 {
+            if i != j {
+                edges.push((i, j + 4));
+            }
+        }
+    }
+    let crown = graph(8, &edges);
+
+    let interleaved = crown.greedy_coloring([0, 4, 1, 5, 2, 6, 3, 7]).unwrap();
+    assert!(is_proper(&crown, &interleaved));
+    assert_eq!(count(&interleaved), 4);
+
+    let by_rows = crown.greedy_coloring([0, 1, 2, 3]).unwrap();
+    assert!(is_proper(&crown, &by_rows));
+    assert_eq!(count(&by_rows), 2);
+    assert!(matches!(crown.greedy_coloring([9]), Err(ColoringError::NodeNotFound)));
+
+    let dsatur = crown.dsatur_coloring();
+    assert!(is_proper(&crown, &dsatur));
+    assert_eq!(count(&dsatur), 2);
+    assert_eq!(crown.chromatic_number(), 2);
+
+    // Odd cycle, complete graph and Petersen graph
+    let c5 = graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+    assert_eq!(c5.chromatic_number(), 3);
+    let k4 = graph(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    assert_eq!(k4.chromatic_number(), 4);
+    let mut petersen = vec![];
+    for i in 0..5 {
+        petersen.extend([(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)]);
+    }
+    let petersen = graph(10, &petersen);
+    let minimum = petersen.minimum_coloring();
+    assert!(is_proper(&petersen, &minimum));
+    assert_eq!(count(&minimum), 3);
+
+    // Grötzsch graph: triangle free, yet needs 4 colours
+    let mut grotzsch = vec![];
+    for i in 0..5 {
+        grotzsch.extend([(i, (i + 1) % 5), (i + 5, (i + 1) % 5)]);
+        grotzsch.extend([(i + 5, (i + 4) % 5), (i + 5, 10)]);
+    }
+    let grotzsch = graph(11, &grotzsch);
+    let minimum = grotzsch.minimum_coloring();
+    assert!(is_proper(&grotzsch, &minimum));
+    assert_eq!(count(&minimum), 4);
+
+    assert_eq!(graph(0, &[]).chromatic_number(), 0);
+    assert_eq!(graph(3, &[]).chromatic_number(), 1);
+}
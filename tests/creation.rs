@@ -226,3 +226,96 @@ fn batch_adjacencies() {
 
     assert!(g.adjacent_nodes('F').is_none());
 }
+
+#[test]
+fn other_kinds_of_names() {
+    // 10 ── 20 ── 30
+    let init = [
+        [false, true, false],
+        [true, false, true],
+        [false, true, false],
+    ];
+    let g = Graph::<(), u64>::from_matrix([10, 20, 30], [(); 3], init).unwrap();
+
+    assert!(g.has_adjacency(10, 20).unwrap());
+    assert!(!g.has_adjacency(10, 30).unwrap());
+    assert!(g.has_adjacency(10, 40).is_none());
+    assert!(Graph::<(), u64>::from_matrix([10, 20, 10], [(); 3], init).is_err());
+
+    let lisboa = Node::new(String::from("Lisboa"), 545_000);
+    let porto = Node::new(String::from("Porto"), 232_000);
+    let faro = Node::new(String::from("Faro"), 61_000);
+
+    let mut g = Graph::from_list(vec![
+        (lisboa.clone(), vec![porto.clone(), faro.clone()]),
+        (porto.clone(), vec![lisboa.clone()]),
+        (faro.clone(), vec![lisboa.clone()]),
+    ])
+    .unwrap();
+
+    assert!(g.has_adjacency("Porto".into(), "Lisboa".into()).unwrap());
+    assert!(!g.has_adjacency("Porto".into(), "Faro".into()).unwrap());
+    assert_eq!(g.adjacent_nodes("Lisboa".into()).unwrap(), vec![&porto, &faro]);
+    assert_eq!(g.bfs(&faro).unwrap().distances["Porto"], 2);
+
+    assert!(g.change_node_name("Faro".into(), "Lisboa".into()).is_err());
+    assert!(g.change_node_name("Faro".into(), "Algarve".into()).is_ok());
+    assert!(g.has_adjacency("Algarve".into(), "Lisboa".into()).unwrap());
+}
+
+#[test]
+fn weighted() {
+    //      7
+    //  A ───── B
+    //  │
+    //  │ 2
+    //  │
+    //  C ───── D
+    //     3
+    let init = [
+        //   A        B        C        D
+        [None,    Some(7), Some(2), None   ], // A
+        [Some(7), None,    None,    None   ], // B
+        [Some(2), None,    None,    Some(3)], // C
+        [None,    None,    Some(3), None   ], // D
+    ];
+    let mut init_asym = init;
+    init_asym[3][2] = Some(4);
+
+    assert!(Graph::<(), char, u32>::from_weighted_matrix(['A', 'B', 'C', 'D'], [(); 4], init_asym).is_err());
+    let mut g = Graph::<(), char, u32>::from_weighted_matrix(['A', 'B', 'C', 'D'], [(); 4], init).unwrap();
+
+    assert_eq!(g.edge_weight('A', 'B'), Some(&7));
+    assert_eq!(g.edge_weight('B', 'A'), Some(&7));
+    assert_eq!(g.edge_weight('D', 'C'), Some(&3));
+    assert_eq!(g.edge_weight('A', 'D'), None);
+    assert_eq!(g.edge_weight('A', 'Z'), None);
+    assert!(g.has_adjacency('A', 'C').unwrap());
+    assert_eq!(g.adjacent_edges('C').unwrap(), vec![(&Node::new('A', ()), &2), (&Node::new('D', ()), &3)]);
+
+    assert_eq!(g.add_weighted_edge('B', 'D', 1).unwrap(), None);
+    assert_eq!(g.add_weighted_edge('D', 'B', 5).unwrap(), Some(1));
+    assert_eq!(g.edge_weight('B', 'D'), Some(&5));
+    assert!(g.remove_edge('A', 'B').unwrap());
+    assert_eq!(g.edge_weight('A', 'B'), None);
+
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+    let c = Node::<()>::new('C', ());
+    let d = Node::<()>::new('D', ());
+
+    let from_lists = Graph::from_weighted_list(vec![
+        (a, vec![(b, 7), (c, 2)]),
+        (b, vec![(a, 7)]),
+        (c, vec![(a, 2), (d, 3)]),
+        (d, vec![(c, 3)]),
+    ])
+    .unwrap();
+    assert_eq!(from_lists, Graph::from_weighted_matrix(['A', 'B', 'C', 'D'], [(); 4], init).unwrap());
+
+    // Disagreeing weights, missing the other side, no list for D, loops
+    assert!(Graph::from_weighted_list(vec![(a, vec![(b, 7)]), (b, vec![(a, 8)])]).is_err());
+    assert!(Graph::from_weighted_list(vec![(a, vec![(b, 7)]), (b, vec![])]).is_err());
+    assert!(Graph::from_weighted_list(vec![(c, vec![(d, 3)])]).is_err());
+    assert!(Graph::from_weighted_list(vec![(a, vec![(a, 1)])]).is_err());
+}
@@ -0,0 +1,62 @@
+use graphs::{is_graphical, FromDegreeSequenceError, Graph, Node};
+
+#[test]
+fn degrees() {
+    //  A ── B ── C
+    //  │  ╱
+    //  D       E
+    let [a, b, c, d, e] = ['A', 'B', 'C', 'D', 'E'].map(|c| Node::<()>::new(c, ()));
+    let g = Graph::from_list(vec![
+        (a, vec![b, d]),
+        (b, vec![a, c, d]),
+        (c, vec![b]),
+        (d, vec![a, b]),
+        (e, vec![]),
+    ])
+    .unwrap();
+
+    assert_eq!(g.degree('B'), Some(3));
+    assert_eq!(g.degree('E'), Some(0));
+    assert_eq!(g.degree('Z'), None);
+    assert_eq!(g.degree_sequence(), vec![3, 2, 2, 1, 0]);
+    assert_eq!(g.min_degree(), Some(0));
+    assert_eq!(g.max_degree(), Some(3));
+    assert_eq!(g.degree_histogram(), vec![1, 1, 2, 1]);
+    assert!(!g.is_regular());
+
+    let triangle = Graph::from_list(vec![(a, vec![b, c]), (b, vec![a, c]), (c, vec![a, b])]).unwrap();
+    assert!(triangle.is_regular());
+    let empty: Graph<()> = Graph::empty();
+    assert!(empty.is_regular());
+    assert_eq!(empty.max_degree(), None);
+    assert!(empty.degree_histogram().is_empty());
+}
+
+#[test]
+fn graphical_sequences() {
+    assert!(is_graphical(&[3, 2, 2, 1, 0]));
+    assert!(is_graphical(&[3, 3, 3, 3]));
+    assert!(is_graphical(&[]));
+    assert!(!is_graphical(&[1, 1, 1]));
+    assert!(!is_graphical(&[3, 3, 1, 1]));
+    assert!(!is_graphical(&[4, 1, 1, 1]));
+
+    let names = ['A', 'B', 'C', 'D', 'E', 'F'];
+    let nodes = || names.map(|c| Node::<()>::new(c, ())).to_vec();
+    for degrees in [[3, 3, 2, 2, 1, 1], [5, 1, 1, 1, 1, 1], [2, 2, 2, 2, 2, 2], [0, 0, 0, 0, 0, 0]] {
+        assert!(is_graphical(&degrees));
+        let g = Graph::from_degree_sequence(nodes(), &degrees).unwrap();
+        for (name, d) in names.iter().zip(degrees) {
+            assert_eq!(g.degree(*name), Some(d));
+        }
+    }
+
+    assert!(matches!(
+        Graph::from_degree_sequence(nodes(), &[3, 3, 1, 1, 0, 0]),
+        Err(FromDegreeSequenceError::NotGraphical)
+    ));
+    assert!(matches!(
+        Graph::from_degree_sequence(nodes(), &[1, 1]),
+        Err(FromDegreeSequenceError::WrongLength)
+    ));
+}
@@ -0,0 +1,100 @@
+use graphs::{DiGraph, Graph, Node, NodeValueChangeError};
+
+#[test]
+fn directed_basic() {
+    // A ──> B ──> C
+    // ^           │
+    // └───────────┘
+    //       D ──> C
+    let init = [
+        //   A      B      C      D
+        [false, true, false, false], // A
+        [false, false, true, false], // B
+        [true, false, false, false], // C
+        [false, false, true, false], // D
+    ];
+    let mut with_loop = init;
+    with_loop[1][1] = true;
+
+    assert!(DiGraph::<()>::from_matrix(['A', 'B', 'C', 'D'], [(); 4], with_loop).is_err());
+    assert!(DiGraph::<()>::from_matrix(['A', 'B', 'C', 'C'], [(); 4], init).is_err());
+    let g = DiGraph::<()>::from_matrix(['A', 'B', 'C', 'D'], [(); 4], init).unwrap();
+
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+    let c = Node::<()>::new('C', ());
+    let d = Node::<()>::new('D', ());
+
+    assert!(g.has_adjacency('A', 'B').unwrap());
+    assert!(!g.has_adjacency('B', 'A').unwrap());
+    assert!(g.has_adjacency('A', 'Z').is_none());
+
+    assert_eq!(g.successors('C').unwrap(), vec![&a]);
+    assert_eq!(g.predecessors('C').unwrap(), vec![&b, &d]);
+    assert_eq!(g.out_degree('D'), Some(1));
+    assert_eq!(g.in_degree('D'), Some(0));
+    assert_eq!(g.in_degree('C'), Some(2));
+    assert_eq!(g.in_degree('Z'), None);
+
+    // D only shows up as a successor
+    let from_lists = DiGraph::from_list(vec![
+        (a, vec![b]),
+        (b, vec![c]),
+        (c, vec![a]),
+        (d, vec![c]),
+    ])
+    .unwrap();
+    assert_eq!(from_lists, g);
+    assert!(DiGraph::from_list(vec![(a, vec![a])]).is_err());
+
+    let undirected = g.into_undirected();
+    assert!(undirected.has_adjacency('A', 'B').unwrap());
+    assert!(undirected.has_adjacency('B', 'A').unwrap());
+    assert!(undirected.has_adjacency('C', 'D').unwrap());
+    assert!(!undirected.has_adjacency('A', 'D').unwrap());
+
+    let back = DiGraph::from(undirected.clone());
+    assert!(back.has_adjacency('B', 'A').unwrap());
+    assert_eq!(back.into_undirected(), undirected);
+}
+
+#[test]
+fn directed_weighted() {
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+
+    let g = DiGraph::from_weighted_list(vec![(a, vec![(b, 3)]), (b, vec![(a, 5)])]).unwrap();
+    assert_eq!(g.edge_weight('A', 'B'), Some(&3));
+    assert_eq!(g.edge_weight('B', 'A'), Some(&5));
+
+    let undirected: Graph<(), char, i32> = g.into_undirected();
+    assert_eq!(undirected.edge_weight('B', 'A'), Some(&3));
+}
+
+#[test]
+fn directed_node_editing() {
+    // A -> B -> C -> A, plus D -> B
+    let [a, b, c, d] = ['A', 'B', 'C', 'D'].map(|x| Node::new(x, x as u32));
+    let mut g = DiGraph::from_list(vec![(a, vec![b]), (b, vec![c]), (c, vec![a]), (d, vec![b])])
+        .unwrap();
+
+    assert_eq!(g.change_node_value('D', 0).unwrap(), u32::from(b'D'));
+    assert_eq!(g.get_node('D'), Some(&Node::new('D', 0)));
+    assert!(matches!(g.change_node_value('Z', 0), Err(NodeValueChangeError::NodeNotFound)));
+
+    assert_eq!(g.change_node_name('C', 'Z').unwrap(), &u32::from(b'C'));
+    assert!(g.change_node_name('A', 'B').is_err());
+    assert!(g.change_node_name('C', 'Y').is_err());
+    assert_eq!(g.successors('Z').unwrap(), vec![&a]);
+    assert_eq!(g.predecessors('Z').unwrap(), vec![&b]);
+
+    // Both the edges into B and the one out of it go
+    assert_eq!(g.remove_node('B').unwrap(), b);
+    assert!(g.remove_node('B').is_err());
+    assert_eq!(g.get_node('B'), None);
+    assert_eq!(g.successors('A').unwrap(), Vec::<&Node<u32>>::new());
+    assert_eq!(g.predecessors('Z').unwrap(), Vec::<&Node<u32>>::new());
+    assert_eq!(g.out_degree('D'), Some(0));
+    assert_eq!(g.has_adjacency('Z', 'A'), Some(true));
+    assert_eq!(g.has_adjacency('A', 'Z'), Some(false));
+}
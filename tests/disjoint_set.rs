@@ -0,0 +1,59 @@
+use graphs::{DisjointSet, Graph, KeyedDisjointSet, Node};
+
+#[test]
+fn by_index() {
+    let mut s = DisjointSet::new(6);
+    assert_eq!(s.set_count(), 6);
+    assert!(s.union(0, 1));
+    assert!(s.union(2, 3));
+    assert!(s.union(1, 3));
+    assert!(!s.union(0, 2));
+    assert_eq!(s.set_count(), 3);
+    assert!(s.same_set(0, 3));
+    assert!(!s.same_set(0, 4));
+    assert_eq!(s.find(1), s.find(2));
+
+    let x = s.push();
+    assert_eq!(x, 6);
+    assert_eq!(s.len(), 7);
+    assert!(s.union(x, 5));
+    assert_eq!(s.set_count(), 3);
+}
+
+#[test]
+fn by_name() {
+    let mut s = KeyedDisjointSet::new(["north", "south", "east", "west", "north"]);
+    assert_eq!(s.len(), 4);
+    assert_eq!(s.union(&"north", &"south"), Some(true));
+    assert_eq!(s.union(&"south", &"north"), Some(false));
+    assert_eq!(s.union(&"north", &"up"), None);
+    assert_eq!(s.same_set(&"east", &"west"), Some(false));
+    let root = s.find(&"north");
+    assert_eq!(s.find(&"south"), root);
+    assert_eq!(s.find(&"up"), None);
+
+    assert!(s.insert("up"));
+    assert!(!s.insert("up"));
+    assert_eq!(s.union(&"up", &"east"), Some(true));
+    assert_eq!(s.set_count(), 3);
+}
+
+#[test]
+fn components() {
+    // A ── B    C    D ── E ── F
+    //      └─────────┘
+    let [a, b, c, d, e, f] = ['A', 'B', 'C', 'D', 'E', 'F'].map(|c| Node::<()>::new(c, ()));
+    let g = Graph::from_list(vec![
+        (a, vec![b]),
+        (b, vec![a, d]),
+        (c, vec![]),
+        (d, vec![b, e]),
+        (e, vec![d, f]),
+        (f, vec![e]),
+    ])
+    .unwrap();
+
+    let components = g.components_union_find();
+    assert_eq!(components, vec![vec![&a, &b, &d, &e, &f], vec![&c]]);
+    assert_eq!(components.len(), g.component_count());
+}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use graphs::{Color, DrawOptions, Graph, Layout, Node};
+
+#[test]
+fn draw_manual_layout() {
+    // A ──── B      C
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+    let c = Node::<()>::new('C', ());
+
+    let g = Graph::from_list(vec![(a, vec![b]), (b, vec![a]), (c, vec![])]).unwrap();
+
+    let red = Color::new(255, 0, 0);
+    let mut options = DrawOptions {
+        width: 200,
+        height: 100,
+        layout: Layout::Manual(HashMap::from([('A', (20, 50)), ('B', (100, 50)), ('C', (180, 50))])),
+        node_radius: 10,
+        background: Color::WHITE,
+        node_color: red,
+        edge_color: Color::BLACK,
+        label_color: Color::WHITE,
+    };
+
+    let img = g.draw(&options).unwrap();
+    assert_eq!((img.width(), img.height()), (200, 100));
+
+    // Edge between A and B, none between B and C
+    assert_eq!(img.get(60, 50), Some(Color::BLACK));
+    assert_eq!(img.get(140, 50), Some(Color::WHITE));
+    // Rim of the discs, out of the way of the labels
+    assert_eq!(img.get(20, 41), Some(red));
+    assert_eq!(img.get(180, 59), Some(red));
+    assert_eq!(img.get(200, 50), None);
+
+    let ppm = img.to_ppm();
+    assert!(ppm.starts_with(b"P6\n200 100\n255\n"));
+    assert_eq!(ppm.len(), "P6\n200 100\n255\n".len() + 200 * 100 * 3);
+
+    options.layout = Layout::Manual(HashMap::from([('A', (20, 50))]));
+    assert!(g.draw(&options).is_err());
+
+    options.layout = Layout::Circle;
+    assert!(g.draw(&options).is_ok());
+}
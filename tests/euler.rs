@@ -0,0 +1,73 @@
+use graphs::{Graph, Node};
+
+fn check_walk(graph: &Graph<()>, walk: &[(&Node<()>, &Node<()>)], edges: usize) {
+    assert_eq!(walk.len(), edges);
+    for w in walk.windows(2) {
+        assert_eq!(w[0].1, w[1].0);
+    }
+    let mut seen: Vec<(char, char)> =
+        walk.iter().map(|(x, y)| (x.name.min(y.name), x.name.max(y.name))).collect();
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), edges);
+    for (x, y) in walk {
+        assert!(graph.has_adjacency(x.name, y.name).unwrap());
+    }
+}
+
+#[test]
+fn eulerian() {
+    // Two triangles sharing C, plus an isolated F
+    //  A       D
+    //  │ ╲   ╱ │
+    //  │   C   │
+    //  │ ╱   ╲ │
+    //  B       E     F
+    let [a, b, c, d, e, f] = ['A', 'B', 'C', 'D', 'E', 'F'].map(|c| Node::<()>::new(c, ()));
+    let bowtie = Graph::from_list(vec![
+        (a, vec![b, c]),
+        (b, vec![a, c]),
+        (c, vec![a, b, d, e]),
+        (d, vec![c, e]),
+        (e, vec![c, d]),
+        (f, vec![]),
+    ])
+    .unwrap();
+
+    assert!(bowtie.is_eulerian());
+    assert!(bowtie.has_eulerian_path());
+    let circuit = bowtie.eulerian_circuit().unwrap();
+    check_walk(&bowtie, &circuit, 6);
+    assert_eq!(circuit[0].0, circuit[5].1);
+
+    // Without A - B, A and B are the odd ones
+    let mut open = bowtie.clone();
+    open.remove_edge('A', 'B').unwrap();
+    assert!(!open.is_eulerian());
+    assert!(open.has_eulerian_path());
+    assert!(open.eulerian_circuit().is_none());
+    let path = open.eulerian_path().unwrap();
+    check_walk(&open, &path, 5);
+    assert_eq!(path[0].0, &a);
+    assert_eq!(path[4].1, &b);
+
+    // Two separate triangles have even degrees, but no single walk
+    let mut split = bowtie.clone();
+    split.remove_node('C').unwrap();
+    split.add_node(c).unwrap();
+    split.add_edge('A', 'B').unwrap();
+    split.add_edge('A', 'C').unwrap();
+    split.add_edge('B', 'C').unwrap();
+    split.add_edge('D', 'F').unwrap();
+    split.add_edge('E', 'F').unwrap();
+    assert!(!split.is_eulerian());
+    assert!(!split.has_eulerian_path());
+    assert!(split.eulerian_path().is_none());
+
+    let no_edges = Graph::from_list(vec![(a, vec![]), (b, vec![])]).unwrap();
+    assert_eq!(no_edges.eulerian_circuit(), Some(vec![]));
+
+    // A star with three leaves has four odd nodes
+    let star = Graph::from_list(vec![(a, vec![b, c, d]), (b, vec![a]), (c, vec![a]), (d, vec![a])]).unwrap();
+    assert!(!star.has_eulerian_path());
+}
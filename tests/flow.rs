@@ -0,0 +1,99 @@
+use graphs::{DiGraph, Graph, MaxFlowError, Node};
+
+#[test]
+fn directed_flow() {
+    // The classic one from CLRS, max flow 23
+    let [s, v1, v2, v3, v4, t] = ['s', '1', '2', '3', '4', 't'].map(|c| Node::<()>::new(c, ()));
+    let g = DiGraph::from_weighted_list(vec![
+        (s, vec![(v1, 16), (v2, 13)]),
+        (v1, vec![(v3, 12)]),
+        (v2, vec![(v1, 4), (v4, 14)]),
+        (v3, vec![(v2, 9), (t, 20)]),
+        (v4, vec![(v3, 7), (t, 4)]),
+    ])
+    .unwrap();
+
+    let flows = [g.edmonds_karp('s', 't'), g.dinic('s', 't'), g.max_flow('s', 't')];
+    for flow in flows.map(Result::unwrap) {
+        assert_eq!(flow.value, 23);
+
+        // Capacities are respected and flow is kept everywhere but at s and t
+        let mut balance = std::collections::HashMap::new();
+        for (a, b, f) in &flow.flows {
+            assert!(*f <= *g.edge_weight(a.name, b.name).unwrap());
+            *balance.entry(a.name).or_insert(0) -= f;
+            *balance.entry(b.name).or_insert(0) += f;
+        }
+        assert_eq!(balance[&'s'], -23);
+        assert_eq!(balance[&'t'], 23);
+        assert!(['1', '2', '3', '4'].iter().all(|c| balance[c] == 0));
+
+        // The cut's capacity is the flow's value
+        let mut side: Vec<char> = flow.source_side.iter().map(|n| n.name).collect();
+        side.sort();
+        assert_eq!(side, vec!['1', '2', '4', 's']);
+        let cut: i32 = flow
+            .source_side
+            .iter()
+            .flat_map(|a| g.successors(a.name).unwrap().into_iter().map(move |b| (a.name, b.name)))
+            .filter(|(_, b)| !side.contains(b))
+            .map(|(a, b)| g.edge_weight(a, b).unwrap())
+            .sum();
+        assert_eq!(cut, 23);
+    }
+
+    assert!(matches!(g.max_flow('s', 's'), Err(MaxFlowError::SourceIsSink)));
+    assert!(matches!(g.max_flow('s', 'x'), Err(MaxFlowError::SinkNotFound)));
+    assert_eq!(g.max_flow('t', 's').unwrap().value, 0);
+}
+
+#[test]
+fn undirected_flow() {
+    //     3     1
+    //  A ─── B ─── D
+    //  │     │1    │
+    //  └──── C ────┘
+    //     2     5
+    let [a, b, c, d] = ['A', 'B', 'C', 'D'].map(|c| Node::<()>::new(c, ()));
+    let g: Graph<(), char, f64> = Graph::from_weighted_list(vec![
+        (a, vec![(b, 3.0), (c, 2.0)]),
+        (b, vec![(a, 3.0), (c, 1.0), (d, 1.0)]),
+        (c, vec![(a, 2.0), (b, 1.0), (d, 5.0)]),
+        (d, vec![(b, 1.0), (c, 5.0)]),
+    ])
+    .unwrap();
+
+    assert_eq!(g.edmonds_karp('A', 'D').unwrap().value, 4.0);
+    assert_eq!(g.dinic('A', 'D').unwrap().value, 4.0);
+    // Same thing the other way around
+    assert_eq!(g.max_flow('D', 'A').unwrap().value, 4.0);
+    assert_eq!(g.max_flow('A', 'D').unwrap().source_side, vec![&a, &b]);
+
+    // Edmonds–Karp ends up sending some of the flow both ways along an edge here
+    let mut dense: Graph<(), usize, i64> = Graph::empty();
+    for x in 0..6 {
+        dense.add_node(Node::new(x, ())).unwrap();
+    }
+    for (a, b, w) in [
+        (0, 1, 8), (0, 2, 5), (0, 3, 5), (0, 4, 1), (0, 5, 9), (1, 2, 4), (1, 3, 1),
+        (1, 4, 6), (1, 5, 6), (2, 3, 2), (2, 5, 6), (3, 4, 2), (4, 5, 6),
+    ] {
+        dense.add_weighted_edge(a, b, w).unwrap();
+    }
+    for flow in [dense.edmonds_karp(5, 0).unwrap(), dense.dinic(5, 0).unwrap()] {
+        let mut balance = [0; 6];
+        let mut seen = Vec::new();
+        for &(a, b, amount) in &flow.flows {
+            assert!(amount > 0 && amount <= *dense.edge_weight(a.name, b.name).unwrap());
+            // Each edge at most once, whichever way
+            let edge = (a.name.min(b.name), a.name.max(b.name));
+            assert!(!seen.contains(&edge));
+            seen.push(edge);
+            balance[a.name] -= amount;
+            balance[b.name] += amount;
+        }
+        assert_eq!(balance[5], -flow.value);
+        assert_eq!(balance[0], flow.value);
+        assert!(balance[1..5].iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,242 @@
+use graphs::{min_cost_assignment, AssignmentError, Graph, Node, SparseGraph};
+
+/// Next number of a plain LCG, for deterministic pseudo-random cases
+fn next_random(seed: &mut i64) -> i64 {
+    *seed = (*seed * 1103515245 + 12345) % 2147483648;
+    *seed
+}
+
+#[test]
+fn bipartite_matching() {
+    // Workers A-E on the left, shifts 1-5 on the right. Only four shifts can be covered: B, C and
+    // E can only take shifts 1 and 3
+    let names = ['A', 'B', 'C', 'D', 'E', '1', '2', '3', '4', '5'];
+    let [a, b, c, d, e, s1, s2, s3, s4, s5] = names.map(|c| Node::<()>::new(c, ()));
+    let g = Graph::from_list(vec![
+        (a, vec![s1, s2, s4]),
+        (b, vec![s1]),
+        (c, vec![s1, s3]),
+        (d, vec![s3, s4, s5]),
+        (e, vec![s3]),
+        (s1, vec![a, b, c]),
+        (s2, vec![a]),
+        (s3, vec![c, d, e]),
+        (s4, vec![a, d]),
+        (s5, vec![d]),
+    ])
+    .unwrap();
+
+    let matching = g.maximum_bipartite_matching().unwrap();
+    assert_eq!(matching.len(), 4);
+    let mut used: Vec<char> = matching.iter().flat_map(|(x, y)| [x.name, y.name]).collect();
+    used.sort();
+    used.dedup();
+    assert_eq!(used.len(), 8);
+    for (x, y) in &matching {
+        assert!(g.has_adjacency(x.name, y.name).unwrap());
+        assert!(x.name.is_alphabetic());
+    }
+
+    let cover = g.bipartite_vertex_cover().unwrap();
+    assert_eq!(cover.len(), 4);
+    for x in g.nodes() {
+        for y in g.adjacent_nodes(x.name).unwrap() {
+            assert!(cover.contains(&x) || cover.contains(&y));
+        }
+    }
+
+    let triangle = Graph::from_list(vec![(a, vec![b, c]), (b, vec![a, c]), (c, vec![a, b])]).unwrap();
+    assert_eq!(triangle.maximum_bipartite_matching().unwrap_err().len(), 3);
+    assert!(triangle.bipartite_vertex_cover().is_err());
+
+    let empty: Graph<()> = Graph::empty();
+    assert!(empty.maximum_bipartite_matching().unwrap().is_empty());
+
+    // Deterministic pseudo-random bipartite graphs, 0-7 on the left and 8-15 on the right. Only
+    // the shortest augmenting paths get used each phase, but the matching still has to be maximum
+    let mut seed = 777_i64;
+    for _ in 0..20 {
+        let mut g: SparseGraph<(), usize> = SparseGraph::empty();
+        for x in 0..16 {
+            g.add_node(Node::new(x, ())).unwrap();
+        }
+        for x in 0..8 {
+            for y in 8..16 {
+                if next_random(&mut seed) % 5 == 0 {
+                    g.add_weighted_edge(x, y, ()).unwrap();
+                }
+            }
+        }
+        let matching = g.maximum_bipartite_matching().unwrap();
+        assert_eq!(matching.len(), g.maximum_matching().len());
+        assert!(matching.iter().all(|(x, y)| g.has_adjacency(x.name, y.name).unwrap()));
+    }
+}
+
+#[test]
+fn general_matching() {
+    // Two triangles joined by an edge: blossoms on both sides
+    //  A           D
+    //  │ ╲       ╱ │
+    //  │   C ─ E   │
+    //  │ ╱       ╲ │
+    //  B           F
+    let [a, b, c, d, e, f, g] = ['A', 'B', 'C', 'D', 'E', 'F', 'G'].map(|c| Node::<()>::new(c, ()));
+    let g6 = Graph::from_list(vec![
+        (a, vec![b, c]),
+        (b, vec![a, c]),
+        (c, vec![a, b, e]),
+        (d, vec![e, f]),
+        (e, vec![c, d, f]),
+        (f, vec![d, e]),
+    ])
+    .unwrap();
+
+    let matching = g6.maximum_matching();
+    assert_eq!(matching.len(), 3);
+    assert!(g6.is_perfect_matching(&matching));
+    assert!(g6.has_perfect_matching());
+    assert!(!g6.is_perfect_matching(&matching[..2]));
+    assert!(!g6.is_perfect_matching(&[(&a, &b), (&c, &e), (&d, &e)]));
+    assert!(!g6.is_perfect_matching(&[(&a, &d), (&b, &c), (&e, &f)]));
+
+    // The 5-cycle plus a pendant node needs the blossom to be expanded to find the third edge
+    //  A ── B ── C
+    //  │         │
+    //  E ─────── D ── G
+    let c5 = Graph::from_list(vec![
+        (a, vec![b, e]),
+        (b, vec![a, c]),
+        (c, vec![b, d]),
+        (d, vec![c, e, g]),
+        (e, vec![a, d]),
+        (g, vec![d]),
+    ])
+    .unwrap();
+    let matching = c5.maximum_matching();
+    assert_eq!(matching.len(), 3);
+    assert!(c5.is_perfect_matching(&matching));
+
+    // Petersen graph: perfect matching of 5 edges
+    let mut petersen: Graph<(), u32> = Graph::empty();
+    for i in 0..10 {
+        petersen.add_node(Node::new(i, ())).unwrap();
+    }
+    for i in 0..5 {
+        petersen.add_edge(i, (i + 1) % 5).unwrap();
+        petersen.add_edge(i, i + 5).unwrap();
+        petersen.add_edge(i + 5, (i + 2) % 5 + 5).unwrap();
+    }
+    assert_eq!(petersen.maximum_matching().len(), 5);
+    assert!(petersen.has_perfect_matching());
+
+    // A star only ever matches one edge
+    let star = Graph::from_list(vec![(a, vec![b, c, d]), (b, vec![a]), (c, vec![a]), (d, vec![a])]).unwrap();
+    assert_eq!(star.maximum_matching().len(), 1);
+    assert!(!star.has_perfect_matching());
+}
+
+/// Cheapest assignment by trying every permutation, rows <= cols
+fn brute_force(costs: &[i64], rows: usize, cols: usize) -> i64 {
+    fn go(costs: &[i64], r: usize, rows: usize, cols: usize, used: &mut Vec<bool>) -> i64 {
+        if r == rows {
+            return 0;
+        }
+        let mut best = i64::MAX;
+        for c in 0..cols {
+            if !used[c] {
+                used[c] = true;
+                best = best.min(costs[r * cols + c] + go(costs, r + 1, rows, cols, used));
+                used[c] = false;
+            }
+        }
+        best
+    }
+    go(costs, 0, rows, cols, &mut vec![false; cols])
+}
+
+#[test]
+fn assignment() {
+    let costs = [
+        4, 1, 3, //
+        2, 0, 5, //
+        3, 2, 2,
+    ];
+    let (pairs, total) = min_cost_assignment(&costs, 3, 3).unwrap();
+    assert_eq!(total, 5);
+    assert_eq!(pairs, vec![(0, 1), (1, 0), (2, 2)]);
+
+    // Deterministic pseudo-random matrices, square and not, with negative costs too
+    let mut seed = 12345_i64;
+    for (rows, cols) in [(4, 4), (3, 5), (5, 3), (6, 6), (1, 4), (0, 3)] {
+        let costs: Vec<i64> = (0..rows * cols)
+            .map(|_| next_random(&mut seed) % 41 - 10)
+            .collect();
+        let (pairs, total) = min_cost_assignment(&costs, rows, cols).unwrap();
+        assert_eq!(pairs.len(), rows.min(cols));
+        assert_eq!(total, pairs.iter().map(|&(r, c)| costs[r * cols + c]).sum::<i64>());
+
+        let expected = if rows <= cols {
+            brute_force(&costs, rows, cols)
+        } else {
+            let t: Vec<i64> = (0..cols)
+                .flat_map(|c| (0..rows).map(move |r| (r, c)))
+                .map(|(r, c)| costs[r * cols + c])
+                .collect();
+            brute_force(&t, cols, rows)
+        };
+        assert_eq!(total, expected);
+    }
+
+    assert!(matches!(min_cost_assignment(&[1, 2, 3], 2, 2), Err(AssignmentError::WrongDimensions)));
+
+    // Workers A, B against jobs X, Y, Z as a complete bipartite graph
+    let [a, b, x, y, z] = ['A', 'B', 'X', 'Y', 'Z'].map(|c| Node::<()>::new(c, ()));
+    let g: Graph<(), char, f64> = Graph::from_weighted_list(vec![
+        (a, vec![(x, 3.0), (y, 1.5), (z, 4.0)]),
+        (b, vec![(x, 2.0), (y, 1.0), (z, 6.0)]),
+        (x, vec![(a, 3.0), (b, 2.0)]),
+        (y, vec![(a, 1.5), (b, 1.0)]),
+        (z, vec![(a, 4.0), (b, 6.0)]),
+    ])
+    .unwrap();
+    let (pairs, total) = g.min_cost_assignment().unwrap();
+    assert_eq!(total, 3.5);
+    assert_eq!(pairs, vec![(&a, &y), (&b, &x)]);
+
+    let mut incomplete = g.clone();
+    incomplete.remove_edge('A', 'X').unwrap();
+    assert!(matches!(incomplete.min_cost_assignment(), Err(AssignmentError::NotComplete)));
+}
+
+#[test]
+fn unsigned_assignment() {
+    let (pairs, total) = min_cost_assignment(&[4_u32, 1, 2, 3], 2, 2).unwrap();
+    assert_eq!(total, 3);
+    assert_eq!(pairs, vec![(0, 1), (1, 0)]);
+
+    let mut seed = 54321_i64;
+    for (rows, cols) in [(5, 5), (3, 6), (6, 4)] {
+        let costs: Vec<i64> = (0..rows * cols)
+            .map(|_| next_random(&mut seed) % 50)
+            .collect();
+        let unsigned: Vec<u32> = costs.iter().map(|&c| c as u32).collect();
+        let (_, expected) = min_cost_assignment(&costs, rows, cols).unwrap();
+        let (_, total) = min_cost_assignment(&unsigned, rows, cols).unwrap();
+        assert_eq!(total as i64, expected);
+    }
+
+    let [a, b, c, x, y, z] = ['A', 'B', 'C', 'X', 'Y', 'Z'].map(|c| Node::<()>::new(c, ()));
+    let g: Graph<(), char, u32> = Graph::from_weighted_list(vec![
+        (a, vec![(x, 4), (y, 1), (z, 3)]),
+        (b, vec![(x, 2), (y, 0), (z, 5)]),
+        (c, vec![(x, 3), (y, 2), (z, 2)]),
+        (x, vec![(a, 4), (b, 2), (c, 3)]),
+        (y, vec![(a, 1), (b, 0), (c, 2)]),
+        (z, vec![(a, 3), (b, 5), (c, 2)]),
+    ])
+    .unwrap();
+    let (pairs, total) = g.min_cost_assignment().unwrap();
+    assert_eq!(total, 5);
+    assert_eq!(pairs, vec![(&a, &y), (&b, &x), (&c, &z)]);
+}
@@ -51,3 +51,47 @@ fn no_dupes_allowed() {
     assert!(g.change_node_name('C', 'A').is_err()); // Ja esta agafat
     assert!(g.get_node('C').is_some());
 }
+
+#[test]
+fn structural_edits() {
+    //  A ── B    C
+    let init = [
+    //   A      B      C
+        [false, true,  false], // A
+        [true,  false, false], // B
+        [false, false, false], // C
+    ];
+    let mut g = Graph::<u32>::from_matrix(['A', 'B', 'C'], [1, 2, 3], init).unwrap();
+
+    assert!(g.add_node(Node::new('A', 4)).is_err());
+    assert!(g.add_node(Node::new('D', 4)).is_ok());
+    assert_eq!(g.adjacent_nodes('D').unwrap().len(), 0);
+    assert!(g.has_adjacency('A', 'B').unwrap());
+
+    assert!(g.add_edge('D', 'A').unwrap());
+    assert!(!g.add_edge('A', 'D').unwrap());
+    assert!(g.has_adjacency('A', 'D').unwrap());
+    assert!(g.has_adjacency('D', 'A').unwrap());
+
+    assert!(g.add_edge('A', 'A').is_err());
+    assert!(g.add_edge('A', 'Z').is_err());
+
+    assert!(g.toggle_edge('B', 'C').unwrap());
+    assert!(g.has_adjacency('C', 'B').unwrap());
+    assert!(!g.toggle_edge('C', 'B').unwrap());
+    assert!(!g.has_adjacency('B', 'C').unwrap());
+
+    assert!(g.remove_edge('B', 'A').unwrap());
+    assert!(!g.remove_edge('B', 'A').unwrap());
+    assert!(!g.has_adjacency('A', 'B').unwrap());
+
+    // D was last, take out one from the middle instead
+    g.add_edge('B', 'D').unwrap();
+    assert_eq!(g.remove_node('A').unwrap(), Node::new('A', 1));
+    assert!(g.remove_node('A').is_err());
+    assert!(g.get_node('A').is_none());
+    assert!(g.has_adjacency('B', 'D').unwrap());
+    assert!(!g.has_adjacency('C', 'D').unwrap());
+    assert!(!g.has_adjacency('B', 'C').unwrap());
+    assert_eq!(g.adjacent_nodes('D').unwrap(), vec![&Node::new('B', 2)]);
+}
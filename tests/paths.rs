@@ -0,0 +1,217 @@
+use graphs::{AStarError, BellmanFordError, DiGraph, DijkstraError, FloydWarshallError, Graph, Node, SparseGraph};
+
+#[test]
+fn dijkstra() {
+    //     7       1
+    //  A ─── B ─────── C
+    //  │     │         │
+    // 2│    3│        1│
+    //  │     │    9    │
+    //  D ─── E ─────── F       G
+    //     4
+    let [a, b, c, d, e, f, g] = ['A', 'B', 'C', 'D', 'E', 'F', 'G'].map(|c| Node::<()>::new(c, ()));
+    let l = vec![
+        (a, vec![(b, 7), (d, 2)]),
+        (b, vec![(a, 7), (c, 1), (e, 3)]),
+        (c, vec![(b, 1), (f, 1)]),
+        (d, vec![(a, 2), (e, 4)]),
+        (e, vec![(b, 3), (d, 4), (f, 9)]),
+        (f, vec![(c, 1), (e, 9)]),
+        (g, vec![]),
+    ];
+    let graph: Graph<(), char, u32> = Graph::from_weighted_list(l).unwrap();
+
+    let sp = graph.dijkstra('A').unwrap();
+    assert_eq!(sp.distances[&'A'], 0);
+    assert_eq!(sp.distances[&'B'], 7);
+    assert_eq!(sp.distances[&'E'], 6);
+    assert_eq!(sp.distances[&'F'], 9);
+    assert!(!sp.distances.contains_key(&'G'));
+    assert_eq!(sp.predecessors[&'A'], None);
+    assert_eq!(sp.predecessors[&'E'], Some(&d));
+
+    let (path, cost) = graph.shortest_path('D', 'C').unwrap().unwrap();
+    assert_eq!(path, vec![&d, &e, &b, &c]);
+    assert_eq!(cost, 8);
+
+    assert_eq!(graph.shortest_path('A', 'A').unwrap(), Some((vec![&a], 0)));
+    assert_eq!(graph.shortest_path('A', 'G').unwrap(), None);
+    assert!(matches!(graph.dijkstra('Z'), Err(DijkstraError::SourceNotFound)));
+    assert!(matches!(graph.shortest_path('A', 'Z'), Err(DijkstraError::TargetNotFound)));
+
+    let negative: Graph<(), char, f64> =
+        Graph::from_weighted_list(vec![(a, vec![(b, -1.0)]), (b, vec![(a, -1.0)])]).unwrap();
+    assert!(matches!(negative.dijkstra('A'), Err(DijkstraError::NegativeWeight)));
+}
+
+#[test]
+fn dijkstra_directed() {
+    // A ──1──> B ──1──> C
+    // └────────5────────^
+    let [a, b, c] = ['A', 'B', 'C'].map(|c| Node::<()>::new(c, ()));
+    let g = DiGraph::from_weighted_list(vec![(a, vec![(b, 1.5), (c, 5.0)]), (b, vec![(c, 1.0)])])
+        .unwrap();
+
+    assert_eq!(g.shortest_path('A', 'C').unwrap(), Some((vec![&a, &b, &c], 2.5)));
+    assert_eq!(g.shortest_path('C', 'A').unwrap(), None);
+}
+
+#[test]
+fn bellman_ford() {
+    // A ──4──> B ──(-2)──> C ──3──> D
+    // └───────────5─────────^
+    let [a, b, c, d] = ['A', 'B', 'C', 'D'].map(|c| Node::<()>::new(c, ()));
+    let g = DiGraph::from_weighted_list(vec![
+        (a, vec![(b, 4), (c, 5)]),
+        (b, vec![(c, -2)]),
+        (c, vec![(d, 3)]),
+    ])
+    .unwrap();
+
+    let sp = g.bellman_ford('A').unwrap();
+    assert_eq!(sp.distances[&'C'], 2);
+    assert_eq!(sp.distances[&'D'], 5);
+    assert_eq!(sp.predecessors[&'C'], Some(&b));
+    assert!(!g.bellman_ford('B').unwrap().distances.contains_key(&'A'));
+    assert!(matches!(g.bellman_ford('Z'), Err(BellmanFordError::SourceNotFound)));
+
+    // B -> C -> D -> B adds up to -1, D -> A can't be reached from it
+    let mut cyclic = g.clone();
+    cyclic.add_weighted_edge('D', 'B', -2).unwrap();
+    let Err(BellmanFordError::NegativeCycle(mut cycle)) = cyclic.bellman_ford('A') else {
+        panic!("the cycle wasn't found");
+    };
+    while cycle[0] != &b {
+        cycle.rotate_left(1);
+    }
+    assert_eq!(cycle, vec![&b, &c, &d]);
+    assert!(cyclic.bellman_ford('Z').is_err());
+
+    // Undirected: a single negative edge is enough
+    let u: Graph<(), char, i32> = Graph::from_weighted_list(vec![
+        (a, vec![(b, 1)]),
+        (b, vec![(a, 1), (c, -1)]),
+        (c, vec![(b, -1)]),
+        (d, vec![]),
+    ])
+    .unwrap();
+    let Err(BellmanFordError::NegativeCycle(cycle)) = u.bellman_ford('A') else {
+        panic!("the cycle wasn't found");
+    };
+    assert_eq!(cycle.len(), 2);
+    assert!(u.bellman_ford('D').is_ok());
+}
+
+#[test]
+fn all_pairs() {
+    // A ──1── B ──2── C ──1── D       E
+    // └───────────5───────────┘
+    let [a, b, c, d, e] = ['A', 'B', 'C', 'D', 'E'].map(|c| Node::<()>::new(c, ()));
+    let g: Graph<(), char, u32> = Graph::from_weighted_list(vec![
+        (a, vec![(b, 1), (d, 5)]),
+        (b, vec![(a, 1), (c, 2)]),
+        (c, vec![(b, 2), (d, 1)]),
+        (d, vec![(a, 5), (c, 1)]),
+        (e, vec![]),
+    ])
+    .unwrap();
+    let pos = |x: char| g.nodes().iter().position(|n| n.name == x).unwrap();
+
+    let fw = g.floyd_warshall().unwrap();
+    assert_eq!(fw.distances.len(), 25);
+    assert_eq!(fw.distance(pos('A'), pos('D')), Some(&4));
+    assert_eq!(fw.distance(pos('D'), pos('A')), Some(&4));
+    assert_eq!(fw.distance(pos('C'), pos('C')), Some(&0));
+    assert_eq!(fw.distance(pos('A'), pos('E')), None);
+    assert_eq!(fw.path(pos('A'), pos('D')), Some(vec![pos('A'), pos('B'), pos('C'), pos('D')]));
+    assert_eq!(fw.path(pos('E'), pos('E')), Some(vec![pos('E')]));
+    assert_eq!(fw.path(pos('E'), pos('A')), None);
+
+    // Same as running dijkstra from everywhere
+    for x in g.nodes() {
+        let sp = g.dijkstra(x.name).unwrap();
+        for y in g.nodes() {
+            assert_eq!(fw.distance(pos(x.name), pos(y.name)), sp.distances.get(&y.name));
+        }
+    }
+
+    let hops = g.distance_matrix();
+    assert_eq!(hops[pos('A') * 5 + pos('D')], Some(1));
+    assert_eq!(hops[pos('B') * 5 + pos('D')], Some(2));
+    assert_eq!(hops[pos('B') * 5 + pos('E')], None);
+
+    let directed = DiGraph::from_weighted_list(vec![(a, vec![(b, -1)]), (b, vec![(c, -1)])]).unwrap();
+    let fw = directed.floyd_warshall().unwrap();
+    assert_eq!(fw.distance(0, 2), Some(&-2));
+    assert_eq!(fw.distance(2, 0), None);
+    assert_eq!(
+        directed.distance_matrix(),
+        vec![Some(0), Some(1), Some(2), None, Some(0), Some(1), None, None, Some(0)]
+    );
+
+    let mut cyclic = directed.clone();
+    cyclic.add_weighted_edge('C', 'A', 1).unwrap();
+    assert!(cyclic.floyd_warshall().is_err());
+
+    // Every pair of nodes makes a negative cycle. Going around them for every k would overflow i32
+    let mut dense: DiGraph<(), u32, i32> = DiGraph::empty();
+    for x in 0..40 {
+        dense.add_node(Node::new(x, ())).unwrap();
+    }
+    for x in 0..40 {
+        for y in 0..40 {
+            if x != y {
+                dense.add_weighted_edge(x, y, -1000).unwrap();
+            }
+        }
+    }
+    assert!(matches!(dense.floyd_warshall(), Err(FloydWarshallError::NegativeCycle)));
+}
+
+#[test]
+fn astar() {
+    // 6x6 grid with a wall down the middle, except for its top row. Node values are coordinates
+    let mut g: SparseGraph<(i32, i32), u32, u32> = SparseGraph::empty();
+    let name = |x: i32, y: i32| (y * 6 + x) as u32;
+    for y in 0..6 {
+        for x in 0..6 {
+            g.add_node(Node::new(name(x, y), (x, y))).unwrap();
+        }
+    }
+    for y in 0..6 {
+        for x in 0..6 {
+            if x < 5 && !(x == 2 && y > 0) {
+                g.add_weighted_edge(name(x, y), name(x + 1, y), 1).unwrap();
+            }
+            if y < 5 {
+                g.add_weighted_edge(name(x, y), name(x, y + 1), 1).unwrap();
+            }
+        }
+    }
+
+    let goal = (5, 5);
+    let manhattan = |n: &Node<(i32, i32), u32>| {
+        ((goal.0 - n.value.0).abs() + (goal.1 - n.value.1).abs()) as u32
+    };
+
+    let search = g.astar(name(0, 5), name(5, 5), manhattan).unwrap();
+    let (path, cost) = search.path.unwrap();
+    assert_eq!(cost, 15);
+    assert_eq!(path.len(), 16);
+    assert_eq!(path[0].value, (0, 5));
+    assert_eq!(path[15].value, (5, 5));
+    assert_eq!(g.shortest_path(name(0, 5), name(5, 5)).unwrap().unwrap().1, cost);
+
+    let blind = g.astar(name(0, 5), name(5, 5), |_| 0).unwrap();
+    assert_eq!(blind.path.unwrap().1, cost);
+    assert!(search.expanded <= blind.expanded);
+
+    let far = g.astar(name(0, 0), name(1, 0), manhattan).unwrap();
+    assert_eq!(far.path.unwrap().1, 1);
+
+    g.add_node(Node::new(100, (9, 9))).unwrap();
+    let unreachable = g.astar(name(0, 0), 100, manhattan).unwrap();
+    assert!(unreachable.path.is_none());
+    assert_eq!(unreachable.expanded, 36);
+    assert!(matches!(g.astar(name(0, 0), 101, manhattan), Err(AStarError::GoalNotFound)));
+}
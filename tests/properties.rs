@@ -0,0 +1,250 @@
+use graphs::{BlockCutNode, Graph, Node};
+
+#[test]
+fn bipartite() {
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+    let c = Node::<()>::new('C', ());
+    let d = Node::<()>::new('D', ());
+    let e = Node::<()>::new('E', ());
+    let f = Node::<()>::new('F', ());
+    let g = Node::<()>::new('G', ());
+    let h = Node::<()>::new('H', ());
+    let i = Node::<()>::new('I', ());
+
+    // Square plus a lone node
+    let square_l = vec![
+        (a, vec![b, d]),
+        (b, vec![a, c]),
+        (c, vec![b, d]),
+        (d, vec![a, c]),
+        (e, vec![]),
+    ];
+
+    // A square (fine) and, in another component, a pentagon (not fine)
+    let pentagon_l = vec![
+        (a, vec![b, d]),
+        (b, vec![a, c]),
+        (c, vec![b, d]),
+        (d, vec![a, c]),
+        (e, vec![f, g]),
+        (f, vec![e, h]),
+        (g, vec![e, i]),
+        (h, vec![f, i]),
+        (i, vec![g, h]),
+    ];
+
+    let square = Graph::from_list(square_l).unwrap();
+    let pentagon = Graph::from_list(pentagon_l).unwrap();
+
+    assert!(square.is_bipartite());
+    let parts = square.bipartition().unwrap();
+    assert_eq!(parts.left, vec![&a, &c, &e]);
+    assert_eq!(parts.right, vec![&b, &d]);
+
+    assert!(!pentagon.is_bipartite());
+    let cycle = pentagon.bipartition().unwrap_err();
+    assert_eq!(cycle.len(), 5);
+    for (k, x) in cycle.iter().enumerate() {
+        let y = cycle[(k + 1) % cycle.len()];
+        assert!(pentagon.has_adjacency(x.name, y.name).unwrap());
+    }
+}
+
+#[test]
+fn components() {
+    //   A ── B    C ── D    E ── F
+    //                  │    │    │
+    //                  G    H ── I
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+    let c = Node::<()>::new('C', ());
+    let d = Node::<()>::new('D', ());
+    let e = Node::<()>::new('E', ());
+    let f = Node::<()>::new('F', ());
+    let g = Node::<()>::new('G', ());
+    let h = Node::<()>::new('H', ());
+    let i = Node::<()>::new('I', ());
+
+    let forest_l = vec![
+        (a, vec![b]),
+        (b, vec![a]),
+        (c, vec![d]),
+        (d, vec![c, g]),
+        (g, vec![d]),
+    ];
+    let with_cycle_l = vec![
+        (a, vec![b]),
+        (b, vec![a]),
+        (e, vec![f, h]),
+        (f, vec![e, i]),
+        (h, vec![e, i]),
+        (i, vec![f, h]),
+    ];
+
+    let forest = Graph::from_list(forest_l).unwrap();
+    let with_cycle = Graph::from_list(with_cycle_l).unwrap();
+
+    assert_eq!(forest.component_count(), 2);
+    assert_eq!(forest.connected_components(), vec![vec![&a, &b], vec![&c, &d, &g]]);
+    assert!(!forest.is_connected());
+    assert!(forest.is_forest());
+    assert!(!forest.is_tree());
+
+    // The first component is a tree, but the graph isn't
+    assert_eq!(with_cycle.component_count(), 2);
+    assert!(!with_cycle.is_forest());
+    assert!(!with_cycle.is_tree());
+
+    let single = Graph::from_list(vec![(c, vec![d]), (d, vec![c, g]), (g, vec![d])]).unwrap();
+    assert!(single.is_connected());
+    assert!(single.is_tree());
+}
+
+#[test]
+fn cycles() {
+    //   A ── B ── C ── D       G ── H
+    //        │         │
+    //        F ─────── E
+    //        │         │
+    //        I ─────── J
+    let names = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J'];
+    let nodes = names.map(|c| Node::<()>::new(c, ()));
+    let [a, b, c, d, e, f, g, h, i, j] = nodes;
+
+    let l = vec![
+        (a, vec![b]),
+        (b, vec![a, c, f]),
+        (c, vec![b, d]),
+        (d, vec![c, e]),
+        (e, vec![d, f, j]),
+        (f, vec![b, e, i]),
+        (g, vec![h]),
+        (h, vec![g]),
+        (i, vec![f, j]),
+        (j, vec![e, i]),
+    ];
+    let graph = Graph::from_list(l).unwrap();
+
+    let cycle = graph.find_cycle().unwrap();
+    assert!(cycle.len() >= 3);
+    for (k, x) in cycle.iter().enumerate() {
+        let y = cycle[(k + 1) % cycle.len()];
+        assert!(graph.has_adjacency(x.name, y.name).unwrap());
+    }
+    let mut names: Vec<char> = cycle.iter().map(|n| n.name).collect();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), cycle.len());
+
+    assert_eq!(graph.girth(), Some(4));
+
+    let tree = Graph::from_list(vec![(a, vec![b]), (b, vec![a, c]), (c, vec![b]), (g, vec![])]).unwrap();
+    assert!(tree.find_cycle().is_none());
+    assert_eq!(tree.girth(), None);
+
+    let triangle = Graph::from_list(vec![(a, vec![b, c]), (b, vec![a, c]), (c, vec![a, b])]).unwrap();
+    assert_eq!(triangle.find_cycle().unwrap().len(), 3);
+    assert_eq!(triangle.girth(), Some(3));
+}
+
+#[test]
+fn cut_vertices_and_bridges() {
+    //  A ── B          F ── G       I
+    //  │  ╱           │  ╱
+    //  C ── D ── E    H
+    let [a, b, c, d, e, f, g, h, i] =
+        ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I'].map(|c| Node::<()>::new(c, ()));
+    let l = vec![
+        (a, vec![b, c]),
+        (b, vec![a, c]),
+        (c, vec![a, b, d]),
+        (d, vec![c, e]),
+        (e, vec![d]),
+        (f, vec![g, h]),
+        (g, vec![f, h]),
+        (h, vec![f, g]),
+        (i, vec![]),
+    ];
+    let graph = Graph::from_list(l).unwrap();
+
+    assert_eq!(graph.articulation_points(), vec![&c, &d]);
+    assert_eq!(graph.bridges(), vec![(&c, &d), (&d, &e)]);
+
+    // A path: every inner node and every edge
+    let path = Graph::from_list(vec![(a, vec![b]), (b, vec![a, c]), (c, vec![b])]).unwrap();
+    assert_eq!(path.articulation_points(), vec![&b]);
+    assert_eq!(path.bridges().len(), 2);
+
+    // A star's centre is the root of the DFS
+    let star = Graph::from_list(vec![(a, vec![b, c]), (b, vec![a]), (c, vec![a])]).unwrap();
+    assert_eq!(star.articulation_points(), vec![&a]);
+}
+
+#[test]
+fn blocks() {
+    //  A ── B          F ── G       I
+    //  │  ╱ │          │  ╱
+    //  C ── D ── E     H
+    let [a, b, c, d, e, f, g, h, i] =
+        ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I'].map(|c| Node::<()>::new(c, ()));
+    let l = vec![
+        (a, vec![b, c]),
+        (b, vec![a, c, d]),
+        (c, vec![a, b, d]),
+        (d, vec![b, c, e]),
+        (e, vec![d]),
+        (f, vec![g, h]),
+        (g, vec![f, h]),
+        (h, vec![f, g]),
+        (i, vec![]),
+    ];
+    let graph = Graph::from_list(l).unwrap();
+
+    let mut blocks: Vec<Vec<char>> = graph
+        .biconnected_components()
+        .into_iter()
+        .map(|b| {
+            let mut names: Vec<char> = b.into_iter().flat_map(|(x, y)| [x.name, y.name]).collect();
+            names.sort();
+            names.dedup();
+            names
+        })
+        .collect();
+    blocks.sort();
+    assert_eq!(blocks, vec![vec!['A', 'B', 'C', 'D'], vec!['D', 'E'], vec!['F', 'G', 'H']]);
+    assert_eq!(graph.biconnected_components().iter().map(Vec::len).sum::<usize>(), 9);
+
+    assert_eq!(
+        graph.two_edge_connected_components(),
+        vec![vec![&a, &b, &c, &d], vec![&e], vec![&f, &g, &h], vec![&i]]
+    );
+
+    // [A B C D] ── D ── [D E]     [F G H]
+    let tree = graph.block_cut_tree();
+    assert_eq!(tree.nodes().len(), 4);
+    assert!(tree.is_forest());
+    assert_eq!(tree.component_count(), 2);
+    let cut = tree.nodes().iter().find(|n| n.value == BlockCutNode::Cut(&d)).unwrap();
+    let around = tree.adjacent_nodes(cut.name).unwrap();
+    assert_eq!(around.len(), 2);
+    assert!(around.iter().any(|n| n.value == BlockCutNode::Block(vec![&d, &e])));
+    assert!(around.iter().any(|n| n.value == BlockCutNode::Block(vec![&a, &b, &c, &d])));
+}
+
+#[test]
+fn block_cut_tree_of_a_long_path() {
+    const N: usize = 1500;
+    let l: Vec<_> = (0..N)
+        .map(|i| {
+            let around = [i.checked_sub(1), Some(i + 1).filter(|&j| j < N)];
+            (Node::new(i, ()), around.into_iter().flatten().map(|j| Node::new(j, ())).collect())
+        })
+        .collect();
+    let graph = Graph::from_list(l).unwrap();
+
+    // N - 1 blocks (the edges) and N - 2 cuts (everything but the ends)
+    let tree = graph.block_cut_tree();
+    assert_eq!(tree.nodes().len(), 2 * N - 3);
+    assert!(tree.is_tree());
+}
@@ -0,0 +1,57 @@
+use graphs::{Graph, Node, SparseGraph};
+
+fn total(edges: &[(&Node<()>, &Node<()>, &u32)]) -> u32 {
+    edges.iter().map(|(_, _, w)| **w).sum()
+}
+
+#[test]
+fn minimum_spanning_forest() {
+    //     4       8
+    //  A ─── B ─────── C       F ─3─ G
+    //  │   ╱ │         │        ╲   ╱
+    // 1│ 2╱  │6       7│        1╲ ╱2
+    //  │ ╱   │    5    │          H
+    //  D ─── E ─────── I
+    //     3
+    let [a, b, c, d, e, f, g, h, i] =
+        ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I'].map(|c| Node::<()>::new(c, ()));
+    let l = vec![
+        (a, vec![(b, 4), (d, 1)]),
+        (b, vec![(a, 4), (c, 8), (d, 2), (e, 6)]),
+        (c, vec![(b, 8), (i, 7)]),
+        (d, vec![(a, 1), (b, 2), (e, 3)]),
+        (e, vec![(b, 6), (d, 3), (i, 5)]),
+        (f, vec![(g, 3), (h, 1)]),
+        (g, vec![(f, 3), (h, 2)]),
+        (h, vec![(f, 1), (g, 2)]),
+        (i, vec![(c, 7), (e, 5)]),
+    ];
+    let graph: Graph<(), char, u32> = Graph::from_weighted_list(l).unwrap();
+
+    let kruskal = graph.kruskal();
+    let prim = graph.prim();
+    assert_eq!(kruskal.len(), 7);
+    assert_eq!(prim.len(), 7);
+    assert_eq!(total(&kruskal), 1 + 2 + 3 + 5 + 7 + 1 + 2);
+    assert_eq!(total(&prim), total(&kruskal));
+    assert_eq!(kruskal[0], (&a, &d, &1));
+    assert_eq!(prim[0], (&a, &d, &1));
+    assert_eq!(graph.minimum_spanning_tree(), kruskal);
+
+    let forest = graph.minimum_spanning_tree_graph();
+    assert_eq!(forest.nodes(), graph.nodes());
+    assert!(forest.is_forest());
+    assert_eq!(forest.component_count(), 2);
+    assert_eq!(forest.edge_weight('I', 'C'), Some(&7));
+    assert_eq!(forest.edge_weight('A', 'B'), None);
+
+    // The forest keeps the storage of the graph it came from
+    let sparse: SparseGraph<(), char, u32> = graph.clone().into_storage();
+    let sparse_forest = sparse.minimum_spanning_tree_graph();
+    assert_eq!(sparse_forest.clone().into_storage(), forest);
+    assert_eq!(sparse_forest.edge_weight('H', 'F'), Some(&1));
+
+    let empty: Graph<(), char, u32> = Graph::empty();
+    assert!(empty.kruskal().is_empty());
+    assert!(empty.prim().is_empty());
+}
@@ -0,0 +1,145 @@
+use graphs::{AdjList, DiGraph, Graph, Node, SparseDiGraph, SparseGraph};
+
+#[test]
+fn sparse_matches_dense() {
+    //   A ── E ── D
+    //   │    │
+    //   B ── C
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+    let c = Node::<()>::new('C', ());
+    let d = Node::<()>::new('D', ());
+    let e = Node::<()>::new('E', ());
+
+    let init = vec![
+        (a, vec![e, b]),
+        (b, vec![a, c]),
+        (c, vec![e, b]),
+        (d, vec![e]),
+        (e, vec![a, c, d]),
+    ];
+
+    let dense = Graph::from_list(init).unwrap();
+    let mut sparse: SparseGraph<()> = dense.clone().into_storage();
+
+    for x in "ABCDE".chars() {
+        assert_eq!(dense.adjacent_nodes(x), sparse.adjacent_nodes(x));
+        for y in "ABCDEZ".chars() {
+            assert_eq!(dense.has_adjacency(x, y), sparse.has_adjacency(x, y));
+        }
+    }
+    assert_eq!(dense.dfs(&a).unwrap(), sparse.dfs(&a).unwrap());
+    assert_eq!(dense.bfs(&d).unwrap().order, sparse.bfs(&d).unwrap().order);
+    assert!(!sparse.is_tree());
+
+    sparse.remove_edge('B', 'C').unwrap();
+    assert!(sparse.is_tree());
+    sparse.remove_node('E').unwrap();
+    assert_eq!(sparse.adjacent_nodes('D').unwrap(), Vec::<&Node<()>>::new());
+    assert!(sparse.has_adjacency('A', 'B').unwrap());
+    assert!(sparse.get_node('E').is_none());
+
+    let mut built = SparseGraph::<()>::empty();
+    for n in [a, b, c, d] {
+        built.add_node(n).unwrap();
+    }
+    built.add_edge('A', 'B').unwrap();
+    assert_eq!(built, sparse);
+    assert_eq!(built.into_storage(), Graph::from_list(vec![(a, vec![b]), (b, vec![a]), (c, vec![]), (d, vec![])]).unwrap());
+}
+
+#[test]
+fn big_sparse_path() {
+    const N: u32 = 200_000;
+
+    let mut g = SparseGraph::<(), u32>::empty();
+    for i in 0..N {
+        g.add_node(Node::new(i, ())).unwrap();
+        if i > 0 {
+            g.add_edge(i - 1, i).unwrap();
+        }
+    }
+
+    assert!(g.is_tree());
+    assert_eq!(g.adjacent_nodes(N / 2).unwrap().len(), 2);
+    assert_eq!(g.dfs(&Node::new(0, ())).unwrap().0.len(), N as usize);
+    assert_eq!(g.bfs(&Node::new(0, ())).unwrap().distances[&(N - 1)], N as usize - 1);
+
+    g.add_edge(0, N - 1).unwrap();
+    assert!(!g.is_tree());
+}
+
+#[test]
+fn dense_built_node_by_node() {
+    const N: u32 = 2000;
+
+    let mut g = Graph::<(), u32>::empty();
+    for i in 0..N {
+        g.add_node(Node::new(i, ())).unwrap();
+        if i > 0 {
+            g.add_edge(i - 1, i).unwrap();
+        }
+    }
+    assert!(g.is_tree());
+    assert_eq!(g.bfs(&Node::new(0, ())).unwrap().distances[&(N - 1)], N as usize - 1);
+
+    // The matrix keeps its spare room, which mustn't show up in comparisons or in new nodes
+    for i in (3..N).rev() {
+        g.remove_node(i).unwrap();
+    }
+    let [a, b, c] = [0, 1, 2].map(|i| Node::new(i, ()));
+    let small = Graph::from_list(vec![(a, vec![b]), (b, vec![a, c]), (c, vec![b])]).unwrap();
+    assert_eq!(g, small);
+    g.remove_node(1).unwrap();
+    g.add_node(Node::new(3, ())).unwrap();
+    assert_eq!(g.adjacent_nodes(0).unwrap(), Vec::<&Node<(), u32>>::new());
+    assert_eq!(g.adjacent_nodes(3).unwrap(), Vec::<&Node<(), u32>>::new());
+}
+
+#[test]
+fn sparse_directed() {
+    let mut g = SparseDiGraph::<()>::empty();
+    for c in "ABC".chars() {
+        g.add_node(Node::new(c, ())).unwrap();
+    }
+    g.add_edge('A', 'B').unwrap();
+    g.add_edge('C', 'B').unwrap();
+    assert!(g.add_edge('A', 'A').is_err());
+
+    assert!(!g.has_adjacency('B', 'A').unwrap());
+    assert_eq!(g.in_degree('B'), Some(2));
+    assert_eq!(g.out_degree('B'), Some(0));
+
+    let dense: DiGraph<()> = g.clone().into_storage();
+    assert_eq!(dense.predecessors('B'), g.predecessors('B'));
+
+    let undirected: Graph<(), char, (), AdjList> = g.into_undirected();
+    assert!(undirected.has_adjacency('B', 'C').unwrap());
+}
+
+#[test]
+fn sparse_from_lists() {
+    // Far too big for a matrix
+    const N: u32 = 100_000;
+    let l: Vec<_> = (0..N)
+        .map(|i| {
+            let around = [i.checked_sub(1), Some(i + 1).filter(|&j| j < N)];
+            (Node::new(i, ()), around.into_iter().flatten().map(|j| Node::new(j, ())).collect())
+        })
+        .collect();
+    let g = SparseGraph::<(), u32>::from_list_in(l).unwrap();
+    assert!(g.is_tree());
+    assert_eq!(g.adjacent_nodes(N / 2).unwrap().len(), 2);
+
+    let [a, b, c] = ['A', 'B', 'C'].map(|c| Node::<()>::new(c, ()));
+    let arcs = vec![(a, vec![(b, 3)]), (c, vec![(b, 5), (a, 1)])];
+    let sparse = SparseDiGraph::from_weighted_list_in(arcs.clone()).unwrap();
+    let dense = DiGraph::from_weighted_list(arcs).unwrap();
+    assert_eq!(sparse.clone().into_storage(), dense);
+    assert_eq!(sparse.edge_weight('C', 'B'), Some(&5));
+
+    let m = [[false, true, false], [true, false, true], [false, true, false]];
+    let sparse = SparseGraph::from_matrix_in(['A', 'B', 'C'], [(); 3], m).unwrap();
+    assert_eq!(sparse.into_storage(), Graph::from_matrix(['A', 'B', 'C'], [(); 3], m).unwrap());
+    assert!(SparseGraph::from_list_in(vec![(a, vec![b]), (b, vec![])]).is_err());
+}
@@ -132,3 +132,57 @@ fn trees() {
     assert!(!complete.is_tree());
     assert!(!bowtie.is_tree());
 }
+
+#[test]
+fn bfs_basic() {
+    // Same graph as dfs_advanced:
+    //   A ── E ── D
+    //   │    │
+    //   B ── C
+    let a = Node::<()>::new('A', ());
+    let b = Node::<()>::new('B', ());
+    let c = Node::<()>::new('C', ());
+    let d = Node::<()>::new('D', ());
+    let e = Node::<()>::new('E', ());
+
+    let init = vec![
+        (a, vec![e, b]),
+        (b, vec![a, c]),
+        (c, vec![e, b]),
+        (d, vec![e]),
+        (e, vec![a, c, d]),
+    ];
+
+    let g = Graph::<()>::from_list(init).unwrap();
+    let res = g.bfs(&a).unwrap();
+
+    assert_eq!(res.order, vec![&a, &e, &b, &c, &d]);
+    assert_eq!(res.edges.len(), 4);
+    assert_eq!(res.distances[&'A'], 0);
+    assert_eq!(res.distances[&'B'], 1);
+    assert_eq!(res.distances[&'E'], 1);
+    assert_eq!(res.distances[&'C'], 2);
+    assert_eq!(res.distances[&'D'], 2);
+    assert_eq!(res.parents[&'A'], None);
+    assert_eq!(res.parents[&'D'], Some(&e));
+
+    let disconnected_l = vec![
+        (a, vec![]),
+        (b, vec![e]),
+        (c, vec![d, e]),
+        (d, vec![c]),
+        (e, vec![b, c]),
+    ];
+    let disconnected = Graph::<()>::from_list(disconnected_l).unwrap();
+
+    let res = disconnected.bfs(&a).unwrap();
+    assert_eq!(res.order, vec![&a]);
+    assert!(!res.distances.contains_key(&'B'));
+
+    let res = disconnected.bfs(&b).unwrap();
+    assert_eq!(res.order, vec![&b, &e, &c, &d]);
+    assert_eq!(res.distances[&'D'], 3);
+
+    let z = Node::<()>::new('Z', ());
+    assert!(disconnected.bfs(&z).is_err());
+}